@@ -0,0 +1,159 @@
+//! CSV arbitrary conversion support for Tree.
+
+use std::collections::HashMap;
+
+use crate::tree::Tree;
+
+impl Tree {
+    /// Builds a drill-down tree from CSV data, grouping rows by the given columns.
+    ///
+    /// Requires the `arbitrary-csv` feature.
+    ///
+    /// Rows are grouped by the value in `group_by[0]`, then within each group by
+    /// `group_by[1]`, and so on; groups keep the order in which their value first
+    /// appears. Each row becomes a leaf holding its remaining columns joined with
+    /// `, `. The root node is labeled `csv`. Every row is treated as data, so
+    /// strip a header row first if there is one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the CSV is malformed or a row has no value for one of
+    /// the `group_by` columns; the latter is reported as an I/O error of kind
+    /// [`InvalidData`](std::io::ErrorKind::InvalidData).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let data = "fruit,apple,red\nfruit,banana,yellow\nvegetable,carrot,orange\n";
+    /// let tree = Tree::from_csv_grouped(data, &[0]).unwrap();
+    /// assert_eq!(tree, Tree::Node("csv".to_string(), vec![
+    ///     Tree::Node("fruit".to_string(), vec![
+    ///         Tree::Leaf(vec!["apple, red".to_string()]),
+    ///         Tree::Leaf(vec!["banana, yellow".to_string()])
+    ///     ]),
+    ///     Tree::Node("vegetable".to_string(), vec![
+    ///         Tree::Leaf(vec!["carrot, orange".to_string()])
+    ///     ])
+    /// ]));
+    /// ```
+    pub fn from_csv_grouped(data: &str, group_by: &[usize]) -> Result<Self, csv::Error> {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(data.as_bytes());
+        let mut rows = Vec::new();
+        for (row_index, record) in reader.records().enumerate() {
+            let fields: Vec<String> = record?.iter().map(str::to_string).collect();
+            if let Some(&column) = group_by.iter().find(|&&column| column >= fields.len()) {
+                return Err(csv::Error::from(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("row {} has no column {}", row_index + 1, column),
+                )));
+            }
+            rows.push(fields);
+        }
+        Ok(Tree::Node(
+            "csv".to_string(),
+            Self::group_csv_rows(rows, group_by, group_by),
+        ))
+    }
+
+    // Helper functions for CSV conversion
+
+    /// Groups `rows` by the `remaining` columns; `group_by` lists every grouping
+    /// column so they can be left out of the leaves.
+    fn group_csv_rows(
+        rows: Vec<Vec<String>>,
+        remaining: &[usize],
+        group_by: &[usize],
+    ) -> Vec<Tree> {
+        let Some((&column, rest)) = remaining.split_first() else {
+            return rows
+                .into_iter()
+                .map(|fields| {
+                    let remaining: Vec<String> = fields
+                        .into_iter()
+                        .enumerate()
+                        .filter(|(index, _)| !group_by.contains(index))
+                        .map(|(_, field)| field)
+                        .collect();
+                    Tree::Leaf(vec![remaining.join(", ")])
+                })
+                .collect();
+        };
+        // Groups in first-seen order, with each value's position in `groups`
+        let mut groups: Vec<(String, Vec<Vec<String>>)> = Vec::new();
+        let mut positions: HashMap<String, usize> = HashMap::new();
+        for fields in rows {
+            match positions.get(&fields[column]) {
+                Some(&position) => groups[position].1.push(fields),
+                None => {
+                    positions.insert(fields[column].clone(), groups.len());
+                    groups.push((fields[column].clone(), vec![fields]));
+                }
+            }
+        }
+        groups
+            .into_iter()
+            .map(|(value, group)| Tree::Node(value, Self::group_csv_rows(group, rest, group_by)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_csv_grouped() {
+        let data = "eu,de,berlin\nus,ca,sacramento\neu,fr,paris\neu,de,munich\n";
+        let tree = Tree::from_csv_grouped(data, &[0]).unwrap();
+        assert_eq!(
+            tree,
+            Tree::Node(
+                "csv".to_string(),
+                vec![
+                    Tree::Node(
+                        "eu".to_string(),
+                        vec![
+                            Tree::Leaf(vec!["de, berlin".to_string()]),
+                            Tree::Leaf(vec!["fr, paris".to_string()]),
+                            Tree::Leaf(vec!["de, munich".to_string()]),
+                        ],
+                    ),
+                    Tree::Node(
+                        "us".to_string(),
+                        vec![Tree::Leaf(vec!["ca, sacramento".to_string()])],
+                    ),
+                ],
+            )
+        );
+
+        let nested = Tree::from_csv_grouped(data, &[0, 1]).unwrap();
+        let eu = &nested.children().unwrap()[0];
+        assert_eq!(eu.label(), Some("eu"));
+        assert_eq!(eu.children().unwrap().len(), 2);
+        assert_eq!(
+            eu.children().unwrap()[0],
+            Tree::Node(
+                "de".to_string(),
+                vec![
+                    Tree::Leaf(vec!["berlin".to_string()]),
+                    Tree::Leaf(vec!["munich".to_string()]),
+                ],
+            )
+        );
+    }
+
+    #[test]
+    fn test_from_csv_grouped_missing_column() {
+        let error = Tree::from_csv_grouped("a,b\nc\n", &[1]).unwrap_err();
+        assert_eq!(error.to_string(), "row 2 has no column 1");
+        assert!(matches!(
+            error.kind(),
+            csv::ErrorKind::Io(io) if io.kind() == std::io::ErrorKind::InvalidData
+        ));
+    }
+}
@@ -0,0 +1,129 @@
+//! Environment-variable block conversion support for Tree.
+
+use crate::tree::Tree;
+
+/// A variable's key split into segments, and its value.
+type EnvVariable<'a> = (Vec<&'a str>, &'a str);
+
+impl Tree {
+    /// Builds a tree from `KEY=VALUE` lines, nesting keys by a separator.
+    ///
+    /// Requires the `arbitrary-env` feature.
+    ///
+    /// Each key is split on `split_on`; every segment but the last becomes a
+    /// node shared by all keys with the same prefix, and the variable itself
+    /// becomes a leaf `LAST=VALUE` under it. Groups keep the order in which they
+    /// first appear. Blank lines, `#` comments, lines without `=` and a leading
+    /// `export ` are ignored, so dotenv files and `env` output both work. The
+    /// root node is labeled `env`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let tree = Tree::from_env_block("DATABASE_URL=postgres://db\nDATABASE_POOL=5\nPORT=80\n", '_');
+    /// assert_eq!(tree, Tree::Node("env".to_string(), vec![
+    ///     Tree::Node("DATABASE".to_string(), vec![
+    ///         Tree::Leaf(vec!["URL=postgres://db".to_string()]),
+    ///         Tree::Leaf(vec!["POOL=5".to_string()])
+    ///     ]),
+    ///     Tree::Leaf(vec!["PORT=80".to_string()])
+    /// ]));
+    /// ```
+    pub fn from_env_block(text: &str, split_on: char) -> Self {
+        let variables = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let line = line.strip_prefix("export ").unwrap_or(line);
+                let (key, value) = line.split_once('=')?;
+                Some((key.trim().split(split_on).collect(), value.trim()))
+            })
+            .collect();
+        Tree::Node("env".to_string(), Self::group_env_variables(variables))
+    }
+
+    // Helper functions for environment block conversion
+
+    /// Groups variables by the first segment of their remaining key.
+    fn group_env_variables(variables: Vec<EnvVariable>) -> Vec<Tree> {
+        let mut children: Vec<Tree> = Vec::new();
+        let mut groups: Vec<(usize, Vec<EnvVariable>)> = Vec::new();
+        for (segments, value) in variables {
+            if let [name] = segments.as_slice() {
+                children.push(Tree::Leaf(vec![format!("{name}={value}")]));
+                continue;
+            }
+            let (prefix, rest) = (segments[0], segments[1..].to_vec());
+            match groups
+                .iter_mut()
+                .find(|(index, _)| children[*index].label() == Some(prefix))
+            {
+                Some((_, group)) => group.push((rest, value)),
+                None => {
+                    groups.push((children.len(), vec![(rest, value)]));
+                    children.push(Tree::Node(prefix.to_string(), Vec::new()));
+                }
+            }
+        }
+        for (index, group) in groups {
+            if let Tree::Node(_, grouped) = &mut children[index] {
+                *grouped = Self::group_env_variables(group);
+            }
+        }
+        children
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_env_block() {
+        let tree = Tree::from_env_block("A_B=1\nA_C=2\n", '_');
+        assert_eq!(
+            tree,
+            Tree::Node(
+                "env".to_string(),
+                vec![Tree::Node(
+                    "A".to_string(),
+                    vec![
+                        Tree::Leaf(vec!["B=1".to_string()]),
+                        Tree::Leaf(vec!["C=2".to_string()]),
+                    ],
+                )],
+            )
+        );
+    }
+
+    #[test]
+    fn test_from_env_block_nesting_and_noise() {
+        let text = "# settings\nexport APP_DB_HOST=localhost\n\nHOME=/root\nAPP_DB_PORT=5432\nnot a variable\nAPP_NAME=demo=1\n";
+        let tree = Tree::from_env_block(text, '_');
+        assert_eq!(
+            tree,
+            Tree::Node(
+                "env".to_string(),
+                vec![
+                    Tree::Node(
+                        "APP".to_string(),
+                        vec![
+                            Tree::Node(
+                                "DB".to_string(),
+                                vec![
+                                    Tree::Leaf(vec!["HOST=localhost".to_string()]),
+                                    Tree::Leaf(vec!["PORT=5432".to_string()]),
+                                ],
+                            ),
+                            Tree::Leaf(vec!["NAME=demo=1".to_string()]),
+                        ],
+                    ),
+                    Tree::Leaf(vec!["HOME=/root".to_string()]),
+                ],
+            )
+        );
+    }
+}
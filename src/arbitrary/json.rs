@@ -1,5 +1,7 @@
 //! JSON arbitrary serialization support for Tree.
 
+#[cfg(feature = "incremental")]
+use crate::incremental::IncrementalTree;
 use crate::tree::Tree;
 
 impl Tree {
@@ -30,6 +32,164 @@ impl Tree {
         Ok(Self::from_json_value(&value))
     }
 
+    /// Deserializes arbitrary JSON into a tree that can be converted back without loss.
+    ///
+    /// Requires the `arbitrary-json` feature.
+    ///
+    /// See [`from_json_value_exact`](Self::from_json_value_exact) for the tree layout.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let json_str = r#"{"name": "treelog", "tags": [], "stable": false}"#;
+    /// let tree = Tree::from_arbitrary_json_exact(json_str).unwrap();
+    /// let value: serde_json::Value = serde_json::from_str(json_str).unwrap();
+    /// assert_eq!(tree.to_json_value_exact().unwrap(), value);
+    /// ```
+    pub fn from_arbitrary_json_exact(json_str: &str) -> Result<Self, serde_json::Error> {
+        let value: serde_json::Value = serde_json::from_str(json_str)?;
+        Ok(Self::from_json_value_exact(&value))
+    }
+
+    /// Converts a JSON value into a tree that records every value's type explicitly.
+    ///
+    /// Unlike [`from_arbitrary_json`](Self::from_arbitrary_json), nothing is inferred
+    /// when converting back with [`to_json_value_exact`](Self::to_json_value_exact),
+    /// which returns a value equal to `value`. Objects become `{}` nodes with one
+    /// node per key holding the key's value, and arrays become `[]` nodes with one
+    /// child per item. Scalars become leaves tagged with a sigil: `s:` for strings
+    /// (split into one line per `\n`), `n:` for numbers, `b:` for booleans, and
+    /// `null`.
+    ///
+    /// Requires the `arbitrary-json` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let value = serde_json::json!({"id": 7, "note": "two\nlines"});
+    /// let tree = Tree::from_json_value_exact(&value);
+    /// assert_eq!(tree, Tree::Node("{}".to_string(), vec![
+    ///     Tree::Node("id".to_string(), vec![Tree::new_leaf("n:7")]),
+    ///     Tree::Node("note".to_string(), vec![Tree::new_leaf_lines(vec!["s:two", "lines"])]),
+    /// ]));
+    /// ```
+    pub fn from_json_value_exact(value: &serde_json::Value) -> Self {
+        match value {
+            serde_json::Value::Null => Tree::new_leaf("null"),
+            serde_json::Value::Bool(b) => Tree::new_leaf(format!("b:{b}")),
+            serde_json::Value::Number(n) => Tree::new_leaf(format!("n:{n}")),
+            serde_json::Value::String(s) => {
+                let mut lines: Vec<String> = s.split('\n').map(str::to_string).collect();
+                lines[0].insert_str(0, "s:");
+                Tree::Leaf(lines)
+            }
+            serde_json::Value::Array(items) => Tree::Node(
+                "[]".to_string(),
+                items.iter().map(Self::from_json_value_exact).collect(),
+            ),
+            serde_json::Value::Object(entries) => Tree::Node(
+                "{}".to_string(),
+                entries
+                    .iter()
+                    .map(|(key, value)| {
+                        Tree::Node(key.clone(), vec![Self::from_json_value_exact(value)])
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Converts a tree built by [`from_json_value_exact`](Self::from_json_value_exact) back to JSON.
+    ///
+    /// Returns an error if the tree does not follow that layout.
+    ///
+    /// Requires the `arbitrary-json` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let value = serde_json::json!([{}, [], "", 1.5, null]);
+    /// assert_eq!(Tree::from_json_value_exact(&value).to_json_value_exact().unwrap(), value);
+    /// assert!(Tree::new_leaf("plain").to_json_value_exact().is_err());
+    /// ```
+    pub fn to_json_value_exact(&self) -> Result<serde_json::Value, serde_json::Error> {
+        use serde::de::Error;
+
+        match self {
+            Tree::Node(label, items) if label == "[]" => items
+                .iter()
+                .map(Tree::to_json_value_exact)
+                .collect::<Result<_, _>>()
+                .map(serde_json::Value::Array),
+            Tree::Node(label, entries) if label == "{}" => entries
+                .iter()
+                .map(|entry| match entry {
+                    Tree::Node(key, value) if value.len() == 1 => {
+                        Ok((key.clone(), value[0].to_json_value_exact()?))
+                    }
+                    _ => Err(serde_json::Error::custom(
+                        "exact JSON object entries must be nodes with exactly one child",
+                    )),
+                })
+                .collect::<Result<_, _>>()
+                .map(serde_json::Value::Object),
+            Tree::Node(label, _) => Err(serde_json::Error::custom(format!(
+                "unexpected node `{label}` in exact JSON tree"
+            ))),
+            Tree::Leaf(lines) => {
+                let first = lines.first().map_or("", String::as_str);
+                if let Some(text) = first.strip_prefix("s:") {
+                    let mut string = text.to_string();
+                    for line in &lines[1..] {
+                        string.push('\n');
+                        string.push_str(line);
+                    }
+                    return Ok(serde_json::Value::String(string));
+                }
+                if lines.len() != 1 {
+                    return Err(serde_json::Error::custom(
+                        "exact JSON scalars other than strings must be single-line leaves",
+                    ));
+                }
+                match first {
+                    "null" => Ok(serde_json::Value::Null),
+                    "b:true" => Ok(serde_json::Value::Bool(true)),
+                    "b:false" => Ok(serde_json::Value::Bool(false)),
+                    _ => match first.strip_prefix("n:") {
+                        Some(number) => serde_json::from_str(number).map(serde_json::Value::Number),
+                        None => Err(serde_json::Error::custom(format!(
+                            "untagged leaf `{first}` in exact JSON tree"
+                        ))),
+                    },
+                }
+            }
+        }
+    }
+
+    /// Serializes a tree built by [`from_json_value_exact`](Self::from_json_value_exact) to a JSON string.
+    ///
+    /// Returns an error if the tree does not follow that layout.
+    ///
+    /// Requires the `arbitrary-json` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let tree = Tree::from_arbitrary_json_exact(r#"{"a":[1,"x"]}"#).unwrap();
+    /// assert_eq!(tree.to_arbitrary_json_exact().unwrap(), r#"{"a":[1,"x"]}"#);
+    /// ```
+    pub fn to_arbitrary_json_exact(&self) -> Result<String, serde_json::Error> {
+        self.to_json_value_exact().map(|value| value.to_string())
+    }
+
     // Helper functions for JSON conversion
 
     fn from_json_value(value: &serde_json::Value) -> Self {
@@ -88,6 +248,217 @@ impl Tree {
     }
 }
 
+#[cfg(feature = "incremental")]
+impl IncrementalTree {
+    /// Parses JSON from a reader, adding items to the tree as they are parsed.
+    ///
+    /// Requires the `arbitrary-json` and `incremental` features.
+    ///
+    /// The document is read and converted in a single pass without building an
+    /// intermediate value, so each item is added as soon as its part of the
+    /// input has been read. The items form the same structure as
+    /// [`Tree::from_arbitrary_json`], added as a new root, except that object
+    /// entries keep their document order (and duplicate keys) instead of being
+    /// sorted by key. Wrap the reader in a [`BufReader`](std::io::BufReader)
+    /// for unbuffered sources.
+    ///
+    /// Returns the IDs of the added items in the order they were added; the
+    /// first is the top-level item.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is not valid JSON. Items parsed before the
+    /// error remain in the tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::incremental::IncrementalTree;
+    ///
+    /// let mut tree = IncrementalTree::new();
+    /// let ids = tree.ingest_json_stream(r#"{"name": "treelog"}"#.as_bytes()).unwrap();
+    /// assert_eq!(ids.len(), 2);
+    /// assert_eq!(tree.get_tree(ids[1]).unwrap().lines(), Some(&["\"name\": \"treelog\"".to_string()][..]));
+    /// ```
+    pub fn ingest_json_stream<R: std::io::Read>(
+        &mut self,
+        reader: R,
+    ) -> Result<Vec<usize>, serde_json::Error> {
+        use serde::de::DeserializeSeed;
+
+        let mut ids = Vec::new();
+        let mut deserializer = serde_json::Deserializer::from_reader(reader);
+        JsonStream {
+            tree: self,
+            ids: &mut ids,
+            placement: JsonPlacement::Value(None),
+        }
+        .deserialize(&mut deserializer)?;
+        deserializer.end()?;
+        Ok(ids)
+    }
+}
+
+/// Where a streamed JSON value is attached, mirroring `Tree::from_json_value`.
+#[cfg(feature = "incremental")]
+enum JsonPlacement {
+    /// Under the given parent, or as a root
+    Value(Option<usize>),
+    /// As the value of an object entry with the given key
+    Entry(usize, String),
+}
+
+/// Adds one streamed JSON value, and everything inside it, to an `IncrementalTree`.
+#[cfg(feature = "incremental")]
+struct JsonStream<'s> {
+    tree: &'s mut IncrementalTree,
+    ids: &'s mut Vec<usize>,
+    placement: JsonPlacement,
+}
+
+#[cfg(feature = "incremental")]
+impl JsonStream<'_> {
+    fn add_scalar(self, text: String) {
+        let id = match self.placement {
+            JsonPlacement::Value(parent) => self.tree.add_leaf(text, parent),
+            JsonPlacement::Entry(parent, key) => self
+                .tree
+                .add_leaf(format!("\"{}\": {}", key, text), Some(parent)),
+        };
+        self.ids.push(id);
+    }
+
+    /// Adds the node for a non-empty object or array, returning its ID.
+    fn add_container(&mut self, label: &str) -> usize {
+        let parent = match &self.placement {
+            JsonPlacement::Value(parent) => *parent,
+            JsonPlacement::Entry(parent, key) => {
+                let id = self.tree.add_node(key.clone(), Some(*parent));
+                self.ids.push(id);
+                Some(id)
+            }
+        };
+        let id = self.tree.add_node(label, parent);
+        self.ids.push(id);
+        id
+    }
+
+    fn child(&mut self, placement: JsonPlacement) -> JsonStream<'_> {
+        JsonStream {
+            tree: &mut *self.tree,
+            ids: &mut *self.ids,
+            placement,
+        }
+    }
+}
+
+#[cfg(feature = "incremental")]
+impl<'de> serde::de::DeserializeSeed<'de> for JsonStream<'_> {
+    type Value = ();
+
+    fn deserialize<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+#[cfg(feature = "incremental")]
+impl<'de> serde::de::Visitor<'de> for JsonStream<'_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("any JSON value")
+    }
+
+    fn visit_bool<E>(self, value: bool) -> Result<(), E> {
+        self.add_scalar(value.to_string());
+        Ok(())
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<(), E> {
+        self.add_scalar(value.to_string());
+        Ok(())
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<(), E> {
+        // Like `from_json_value`, numbers beyond `i64` are shown as floats
+        match i64::try_from(value) {
+            Ok(value) => self.add_scalar(value.to_string()),
+            Err(_) => self.add_scalar((value as f64).to_string()),
+        }
+        Ok(())
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<(), E> {
+        self.add_scalar(value.to_string());
+        Ok(())
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<(), E> {
+        self.add_scalar(format!("\"{}\"", value));
+        Ok(())
+    }
+
+    fn visit_unit<E>(self) -> Result<(), E> {
+        self.add_scalar("null".to_string());
+        Ok(())
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(mut self, mut seq: A) -> Result<(), A::Error> {
+        let mut array = None;
+        let mut index = 0;
+        while let Some(()) = seq.next_element_seed(JsonElement {
+            stream: &mut self,
+            array: &mut array,
+            index,
+        })? {
+            index += 1;
+        }
+        if array.is_none() {
+            self.add_scalar("[]".to_string());
+        }
+        Ok(())
+    }
+
+    fn visit_map<A: serde::de::MapAccess<'de>>(mut self, mut map: A) -> Result<(), A::Error> {
+        let mut object = None;
+        while let Some(key) = map.next_key::<String>()? {
+            let parent = *object.get_or_insert_with(|| self.add_container("object"));
+            map.next_value_seed(self.child(JsonPlacement::Entry(parent, key)))?;
+        }
+        if object.is_none() {
+            self.add_scalar("{}".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Adds an array element, creating the array node before the first one.
+#[cfg(feature = "incremental")]
+struct JsonElement<'a, 's> {
+    stream: &'a mut JsonStream<'s>,
+    array: &'a mut Option<usize>,
+    index: usize,
+}
+
+#[cfg(feature = "incremental")]
+impl<'de> serde::de::DeserializeSeed<'de> for JsonElement<'_, '_> {
+    type Value = ();
+
+    fn deserialize<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        let array = *self
+            .array
+            .get_or_insert_with(|| self.stream.add_container("array"));
+        let item = self
+            .stream
+            .tree
+            .add_node(format!("[{}]", self.index), Some(array));
+        self.stream.ids.push(item);
+        self.stream
+            .child(JsonPlacement::Value(Some(item)))
+            .deserialize(deserializer)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -108,10 +479,158 @@ mod tests {
         assert!(tree.is_node());
     }
 
+    #[test]
+    fn test_json_exact_edge_cases() {
+        let value = serde_json::json!({
+            "": {},
+            "empties": [[], {}, [[]], {"": []}, ""],
+            "numbers": [0, -0.0, 1.0, -1, u64::MAX, i64::MIN, 1.5e300, 5e-324, f64::MAX],
+            "strings": ["null", "s:", "n:1", "b:true", "[]", "{}", "\n", "a\nb\n", "\r\n", "\"q\"", "üñí"],
+            "scalars": [true, false, null],
+            "multi\nline key": "{}",
+        });
+        let tree = Tree::from_json_value_exact(&value);
+        assert_eq!(tree.to_json_value_exact().unwrap(), value);
+
+        let json = tree.to_arbitrary_json_exact().unwrap();
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&json).unwrap(),
+            value
+        );
+        assert_eq!(Tree::from_arbitrary_json_exact(&json).unwrap(), tree);
+
+        for scalar in [
+            serde_json::json!(""),
+            serde_json::json!(null),
+            serde_json::json!(2),
+        ] {
+            let tree = Tree::from_json_value_exact(&scalar);
+            assert_eq!(tree.to_json_value_exact().unwrap(), scalar);
+        }
+    }
+
+    #[test]
+    fn test_json_exact_float_roundtrip() {
+        let mut state = 0x9E37_79B9_7F4A_7C15u64;
+        let floats = (0..10_000).map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            f64::from_bits(state)
+        });
+        for float in floats.chain([1.0715660391465826e-75, f64::MIN_POSITIVE, f64::EPSILON]) {
+            let Some(number) = serde_json::Number::from_f64(float) else {
+                continue;
+            };
+            let value = serde_json::Value::Number(number);
+            let tree = Tree::from_json_value_exact(&value);
+            assert_eq!(tree.to_json_value_exact().unwrap(), value);
+            assert_eq!(
+                Tree::from_arbitrary_json_exact(&value.to_string()).unwrap(),
+                tree
+            );
+        }
+    }
+
+    #[test]
+    fn test_json_exact_rejects_other_trees() {
+        assert!(Tree::new_leaf("plain").to_json_value_exact().is_err());
+        assert!(Tree::new_leaf("n:x").to_json_value_exact().is_err());
+        assert!(Tree::Leaf(vec![]).to_json_value_exact().is_err());
+        assert!(
+            Tree::new_leaf_lines(vec!["b:true", "b:false"])
+                .to_json_value_exact()
+                .is_err()
+        );
+        assert!(Tree::new_node("object").to_json_value_exact().is_err());
+        let entry = Tree::Node("{}".to_string(), vec![Tree::new_leaf("s:a")]);
+        assert!(entry.to_json_value_exact().is_err());
+    }
+
+    fn arbitrary_json_value() -> impl proptest::strategy::Strategy<Value = serde_json::Value> {
+        use proptest::prelude::*;
+        use serde_json::Value;
+
+        let special = prop_oneof![
+            Just(""),
+            Just("null"),
+            Just("s:"),
+            Just("n:1"),
+            Just("b:false"),
+            Just("[]"),
+            Just("{}"),
+            Just("\n"),
+            Just("a\n\nb\n"),
+        ];
+        let scalar = prop_oneof![
+            Just(Value::Null),
+            any::<bool>().prop_map(Value::Bool),
+            any::<i64>().prop_map(Value::from),
+            any::<u64>().prop_map(Value::from),
+            any::<f64>().prop_filter_map("JSON numbers are finite", |f| {
+                serde_json::Number::from_f64(f).map(Value::Number)
+            }),
+            any::<String>().prop_map(Value::String),
+            special.prop_map(|s| Value::String(s.to_string())),
+        ];
+        scalar.prop_recursive(4, 64, 6, |inner| {
+            prop_oneof![
+                prop::collection::vec(inner.clone(), 0..6).prop_map(Value::Array),
+                prop::collection::btree_map(any::<String>(), inner, 0..6)
+                    .prop_map(|entries| Value::Object(entries.into_iter().collect())),
+            ]
+        })
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_json_exact_roundtrip(value in arbitrary_json_value()) {
+            let tree = Tree::from_json_value_exact(&value);
+            proptest::prop_assert_eq!(tree.to_json_value_exact().unwrap(), value.clone());
+            proptest::prop_assert_eq!(Tree::from_arbitrary_json_exact(&value.to_string()).unwrap(), tree);
+        }
+    }
+
     #[test]
     fn test_from_arbitrary_json_array() {
         let json_str = r#"{"dependencies": ["serde", "toml"]}"#;
         let tree = Tree::from_arbitrary_json(json_str);
         assert!(tree.is_ok());
     }
+
+    #[cfg(feature = "incremental")]
+    #[test]
+    fn test_ingest_json_stream() {
+        let json_str = r#"{
+  "array": [1, -2, 2.5, {"deep": null}, []],
+  "empty": {},
+  "flag": true,
+  "package": {"name": "treelog", "version": "0.0.4"}
+}"#;
+        let mut tree = IncrementalTree::new();
+        let ids = tree.ingest_json_stream(json_str.as_bytes()).unwrap();
+        assert_eq!(
+            tree.build_tree(),
+            Some(Tree::from_arbitrary_json(json_str).unwrap())
+        );
+        assert_eq!(ids.len(), tree.len());
+        assert_eq!(tree.get_tree(ids[0]).unwrap().label(), Some("object"));
+
+        let mut scalar = IncrementalTree::new();
+        let ids = scalar.ingest_json_stream("\"text\"".as_bytes()).unwrap();
+        assert_eq!(ids.len(), 1);
+        assert_eq!(scalar.build_tree(), Some(Tree::new_leaf("\"text\"")));
+    }
+
+    #[cfg(feature = "incremental")]
+    #[test]
+    fn test_ingest_json_stream_errors() {
+        let mut tree = IncrementalTree::new();
+        assert!(
+            tree.ingest_json_stream(r#"{"a": 1, "b": "#.as_bytes())
+                .is_err()
+        );
+        assert_eq!(tree.len(), 2);
+        assert!(tree.ingest_json_stream("1 2".as_bytes()).is_err());
+    }
 }
@@ -1,9 +1,10 @@
 //! Arbitrary data structure conversion support for Tree.
 //!
 //! This module provides functions to convert arbitrary data structures (JSON, YAML, TOML, XML,
-//! filesystem, Git repositories, Rust AST, tree-sitter parse trees, clap commands, cargo
-//! metadata, and petgraph graphs) to Tree. This is a one-way conversion from arbitrary data
-//! to Tree, separate from the exact Tree serialization in `serde`.
+//! CSV, environment variable blocks, filesystem, Git repositories, Rust AST, tree-sitter parse
+//! trees, clap commands, cargo metadata, and petgraph graphs) to Tree. This is a one-way
+//! conversion from arbitrary data to Tree, separate from the exact Tree serialization in
+//! `serde`.
 
 #[cfg(feature = "arbitrary-json")]
 mod json;
@@ -17,6 +18,12 @@ mod toml;
 #[cfg(feature = "arbitrary-xml")]
 mod xml;
 
+#[cfg(feature = "arbitrary-csv")]
+mod csv;
+
+#[cfg(feature = "arbitrary-env")]
+mod env;
+
 #[cfg(feature = "arbitrary-walkdir")]
 mod walkdir;
 
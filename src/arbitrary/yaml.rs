@@ -62,10 +62,7 @@ impl Tree {
                 let children: Vec<Tree> = map
                     .iter()
                     .map(|(key, val)| {
-                        let key_str = match key {
-                            serde_yaml::Value::String(s) => s.clone(),
-                            _ => format!("{:?}", key),
-                        };
+                        let key_str = Self::yaml_key_label(key);
                         let child = Self::from_yaml_value(val);
                         if child.is_leaf() {
                             let leaf_lines = child.lines().unwrap();
@@ -91,6 +88,22 @@ impl Tree {
             }
         }
     }
+
+    /// Formats a mapping key the way it is written in YAML, so that parsing the
+    /// label as a YAML scalar yields the original key (`1`, `true`, `null`).
+    fn yaml_key_label(key: &serde_yaml::Value) -> String {
+        match key {
+            serde_yaml::Value::String(s) => s.clone(),
+            serde_yaml::Value::Number(n) => n.to_string(),
+            serde_yaml::Value::Bool(b) => b.to_string(),
+            serde_yaml::Value::Null => "null".to_string(),
+            serde_yaml::Value::Tagged(tagged) => Self::yaml_key_label(&tagged.value),
+            // Complex keys are rare; fall back to their flattened YAML form
+            _ => serde_yaml::to_string(key)
+                .map(|s| s.trim_end().replace('\n', " "))
+                .unwrap_or_else(|_| format!("{:?}", key)),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -120,4 +133,37 @@ dependencies:
         let tree = Tree::from_arbitrary_yaml(yaml_str);
         assert!(tree.is_ok());
     }
+
+    #[test]
+    fn test_from_arbitrary_yaml_scalar_keys() {
+        let yaml_str = r#"
+1: one
+2.5: two and a half
+true: yes
+null: nothing
+"#;
+        let tree = Tree::from_arbitrary_yaml(yaml_str).unwrap();
+        let labels: Vec<&str> = tree
+            .children()
+            .unwrap()
+            .iter()
+            .map(|child| child.lines().unwrap()[0].as_str())
+            .collect();
+        assert_eq!(
+            labels,
+            vec![
+                "1: \"one\"",
+                "2.5: \"two and a half\"",
+                "true: \"yes\"",
+                "null: \"nothing\"",
+            ]
+        );
+
+        let original: serde_yaml::Mapping = serde_yaml::from_str(yaml_str).unwrap();
+        for (key, label) in original.keys().zip(&labels) {
+            let (key_label, _) = label.split_once(": ").unwrap();
+            let reparsed: serde_yaml::Value = serde_yaml::from_str(key_label).unwrap();
+            assert_eq!(&reparsed, key);
+        }
+    }
 }
@@ -0,0 +1,366 @@
+//! Compact binary tree format for on-disk caching.
+//!
+//! The format starts with the `TRLG` magic bytes and a little-endian `u16`
+//! version, followed by the root element. Each element is a tag byte (`0` for a
+//! node, `1` for a leaf). A node stores its label and a `u32` child count followed
+//! by its children; a leaf stores a `u32` line count followed by its lines.
+//! Strings are UTF-8 prefixed by their `u32` byte length, and all integers are
+//! little-endian.
+//!
+//! # Examples
+//!
+//! ```
+//! use treelog::Tree;
+//!
+//! let tree = Tree::Node("root".to_string(), vec![
+//!     Tree::Leaf(vec!["item".to_string()])
+//! ]);
+//! let bytes = tree.to_treelog_binary().unwrap();
+//! assert_eq!(Tree::from_treelog_binary(&bytes).unwrap(), tree);
+//! ```
+
+use std::fmt;
+
+use crate::tree::Tree;
+
+/// Magic bytes at the start of every encoded tree.
+pub const MAGIC: &[u8; 4] = b"TRLG";
+
+/// Version of the binary format written by [`Tree::to_treelog_binary`].
+pub const VERSION: u16 = 1;
+
+/// Deepest nesting of elements that can be encoded or decoded, with the root at depth 0.
+///
+/// Decoding is recursive, so the limit keeps crafted or corrupt input from
+/// overflowing the stack.
+pub const MAX_DEPTH: usize = 1024;
+
+const TAG_NODE: u8 = 0;
+const TAG_LEAF: u8 = 1;
+
+/// Errors returned when encoding or decoding the binary tree format.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FormatError {
+    /// The data does not start with the `TRLG` magic bytes
+    InvalidMagic,
+    /// The data was written with a format version this crate cannot read
+    UnsupportedVersion(u16),
+    /// The data ended before the tree was complete
+    UnexpectedEof,
+    /// An element tag byte is neither a node nor a leaf
+    InvalidTag(u8),
+    /// A label or line is not valid UTF-8
+    InvalidUtf8,
+    /// Extra bytes follow the encoded tree
+    TrailingBytes,
+    /// Elements are nested deeper than [`MAX_DEPTH`]
+    TooDeep,
+    /// A label, line or count is too long to store as a `u32` length
+    LengthOverflow(usize),
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormatError::InvalidMagic => write!(f, "not a treelog binary tree: invalid magic"),
+            FormatError::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported treelog binary version {version} (expected {VERSION})"
+            ),
+            FormatError::UnexpectedEof => write!(f, "unexpected end of treelog binary data"),
+            FormatError::InvalidTag(tag) => write!(f, "invalid element tag {tag}"),
+            FormatError::InvalidUtf8 => write!(f, "label or line is not valid UTF-8"),
+            FormatError::TrailingBytes => write!(f, "trailing bytes after encoded tree"),
+            FormatError::TooDeep => write!(f, "tree is nested deeper than {MAX_DEPTH} levels"),
+            FormatError::LengthOverflow(len) => {
+                write!(
+                    f,
+                    "length {len} does not fit in a treelog binary u32 length"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for FormatError {}
+
+impl Tree {
+    /// Encodes the tree in the compact treelog binary format.
+    ///
+    /// Requires the `binary` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FormatError::TooDeep`] if the tree is nested deeper than
+    /// [`MAX_DEPTH`], or [`FormatError::LengthOverflow`] if a label, line or
+    /// count doesn't fit in a `u32`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["item".to_string()])
+    /// ]);
+    /// let bytes = tree.to_treelog_binary().unwrap();
+    /// assert!(bytes.starts_with(b"TRLG"));
+    /// ```
+    pub fn to_treelog_binary(&self) -> Result<Vec<u8>, FormatError> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&VERSION.to_le_bytes());
+        self.write_binary(&mut bytes, 0)?;
+        Ok(bytes)
+    }
+
+    /// Decodes a tree from the compact treelog binary format.
+    ///
+    /// Requires the `binary` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FormatError`] if the magic bytes or version don't match, if
+    /// elements are nested deeper than [`MAX_DEPTH`], or if the data is truncated
+    /// or otherwise malformed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    /// use treelog::binary::FormatError;
+    ///
+    /// let tree = Tree::Leaf(vec!["item".to_string()]);
+    /// let bytes = tree.to_treelog_binary().unwrap();
+    /// assert_eq!(Tree::from_treelog_binary(&bytes).unwrap(), tree);
+    /// assert_eq!(Tree::from_treelog_binary(b"JSON"), Err(FormatError::InvalidMagic));
+    /// ```
+    pub fn from_treelog_binary(bytes: &[u8]) -> Result<Tree, FormatError> {
+        if bytes.len() < MAGIC.len() || &bytes[..MAGIC.len()] != MAGIC {
+            return Err(FormatError::InvalidMagic);
+        }
+        let mut reader = Reader {
+            bytes: &bytes[MAGIC.len()..],
+        };
+        let version = u16::from_le_bytes(reader.take_array()?);
+        if version != VERSION {
+            return Err(FormatError::UnsupportedVersion(version));
+        }
+        let tree = reader.read_tree(0)?;
+        if !reader.bytes.is_empty() {
+            return Err(FormatError::TrailingBytes);
+        }
+        Ok(tree)
+    }
+
+    fn write_binary(&self, bytes: &mut Vec<u8>, depth: usize) -> Result<(), FormatError> {
+        if depth > MAX_DEPTH {
+            return Err(FormatError::TooDeep);
+        }
+        match self {
+            Tree::Node(label, children) => {
+                bytes.push(TAG_NODE);
+                write_str(bytes, label)?;
+                write_len(bytes, children.len())?;
+                for child in children {
+                    child.write_binary(bytes, depth + 1)?;
+                }
+            }
+            Tree::Leaf(lines) => {
+                bytes.push(TAG_LEAF);
+                write_len(bytes, lines.len())?;
+                for line in lines {
+                    write_str(bytes, line)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn write_len(bytes: &mut Vec<u8>, len: usize) -> Result<(), FormatError> {
+    let len = u32::try_from(len).map_err(|_| FormatError::LengthOverflow(len))?;
+    bytes.extend_from_slice(&len.to_le_bytes());
+    Ok(())
+}
+
+fn write_str(bytes: &mut Vec<u8>, s: &str) -> Result<(), FormatError> {
+    write_len(bytes, s.len())?;
+    bytes.extend_from_slice(s.as_bytes());
+    Ok(())
+}
+
+/// Cursor over the encoded bytes after the magic.
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], FormatError> {
+        if self.bytes.len() < len {
+            return Err(FormatError::UnexpectedEof);
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N], FormatError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    fn read_len(&mut self) -> Result<usize, FormatError> {
+        Ok(u32::from_le_bytes(self.take_array()?) as usize)
+    }
+
+    fn read_string(&mut self) -> Result<String, FormatError> {
+        let len = self.read_len()?;
+        let bytes = self.take(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| FormatError::InvalidUtf8)
+    }
+
+    fn read_tree(&mut self, depth: usize) -> Result<Tree, FormatError> {
+        if depth > MAX_DEPTH {
+            return Err(FormatError::TooDeep);
+        }
+        let [tag] = self.take_array()?;
+        match tag {
+            TAG_NODE => {
+                let label = self.read_string()?;
+                let count = self.read_len()?;
+                // Each child takes at least one byte, so cap the reservation by what remains
+                let mut children = Vec::with_capacity(count.min(self.bytes.len()));
+                for _ in 0..count {
+                    children.push(self.read_tree(depth + 1)?);
+                }
+                Ok(Tree::Node(label, children))
+            }
+            TAG_LEAF => {
+                let count = self.read_len()?;
+                let mut lines = Vec::with_capacity(count.min(self.bytes.len() / 4));
+                for _ in 0..count {
+                    lines.push(self.read_string()?);
+                }
+                Ok(Tree::Leaf(lines))
+            }
+            tag => Err(FormatError::InvalidTag(tag)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Tree {
+        Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node(
+                    "dir".to_string(),
+                    vec![
+                        Tree::Leaf(vec!["line 1".to_string(), "line 2".to_string()]),
+                        Tree::Node("empty".to_string(), vec![]),
+                    ],
+                ),
+                Tree::Leaf(vec![]),
+                Tree::Leaf(vec!["ünïcödé ✓".to_string()]),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let tree = sample();
+        let bytes = tree.to_treelog_binary().unwrap();
+        assert_eq!(&bytes[..4], MAGIC);
+        assert_eq!(u16::from_le_bytes([bytes[4], bytes[5]]), VERSION);
+        assert_eq!(Tree::from_treelog_binary(&bytes), Ok(tree));
+
+        let leaf = Tree::Leaf(vec!["only".to_string()]);
+        assert_eq!(
+            Tree::from_treelog_binary(&leaf.to_treelog_binary().unwrap()),
+            Ok(leaf)
+        );
+    }
+
+    #[test]
+    fn test_corrupt_header() {
+        let mut bytes = sample().to_treelog_binary().unwrap();
+
+        let mut bad_magic = bytes.clone();
+        bad_magic[0] = b'X';
+        assert_eq!(
+            Tree::from_treelog_binary(&bad_magic),
+            Err(FormatError::InvalidMagic)
+        );
+        assert_eq!(
+            Tree::from_treelog_binary(b"TR"),
+            Err(FormatError::InvalidMagic)
+        );
+
+        let mut bad_version = bytes.clone();
+        bad_version[4..6].copy_from_slice(&7u16.to_le_bytes());
+        assert_eq!(
+            Tree::from_treelog_binary(&bad_version),
+            Err(FormatError::UnsupportedVersion(7))
+        );
+        assert!(
+            FormatError::UnsupportedVersion(7)
+                .to_string()
+                .contains("version 7")
+        );
+
+        let truncated = &bytes[..bytes.len() - 1];
+        assert_eq!(
+            Tree::from_treelog_binary(truncated),
+            Err(FormatError::UnexpectedEof)
+        );
+
+        let mut bad_tag = bytes.clone();
+        bad_tag[6] = 9;
+        assert_eq!(
+            Tree::from_treelog_binary(&bad_tag),
+            Err(FormatError::InvalidTag(9))
+        );
+
+        bytes.push(0);
+        assert_eq!(
+            Tree::from_treelog_binary(&bytes),
+            Err(FormatError::TrailingBytes)
+        );
+    }
+    #[test]
+    fn test_depth_limit() {
+        // Nodes with an empty label and one child each, nested far past the limit
+        let mut bytes = Vec::from(*MAGIC);
+        bytes.extend_from_slice(&VERSION.to_le_bytes());
+        for _ in 0..1_000_000 {
+            bytes.extend_from_slice(&[TAG_NODE, 0, 0, 0, 0, 1, 0, 0, 0]);
+        }
+        assert_eq!(Tree::from_treelog_binary(&bytes), Err(FormatError::TooDeep));
+
+        let mut tree = Tree::Leaf(vec!["bottom".to_string()]);
+        for _ in 0..MAX_DEPTH {
+            tree = Tree::Node(String::new(), vec![tree]);
+        }
+        let bytes = tree.to_treelog_binary().unwrap();
+        assert_eq!(Tree::from_treelog_binary(&bytes).as_ref(), Ok(&tree));
+
+        let deeper = Tree::Node(String::new(), vec![tree]);
+        assert_eq!(deeper.to_treelog_binary(), Err(FormatError::TooDeep));
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn test_length_overflow() {
+        let len = u32::MAX as usize + 1;
+        assert_eq!(
+            write_len(&mut Vec::new(), len),
+            Err(FormatError::LengthOverflow(len))
+        );
+        assert!(write_len(&mut Vec::new(), u32::MAX as usize).is_ok());
+    }
+}
@@ -84,6 +84,28 @@ impl TreeBuilder {
         self
     }
 
+    /// Reserves capacity for at least `additional` more children in the current node.
+    ///
+    /// Has no effect if no node has been added yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::builder::TreeBuilder;
+    ///
+    /// let mut builder = TreeBuilder::new();
+    /// builder.node("root").reserve(100);
+    /// for i in 0..100 {
+    ///     builder.leaf(format!("item {i}"));
+    /// }
+    /// ```
+    pub fn reserve(&mut self, additional: usize) -> &mut Self {
+        if let Some(Tree::Node(_, children)) = self.stack.last_mut() {
+            children.reserve(additional);
+        }
+        self
+    }
+
     /// Ends the current node context and returns to the parent.
     ///
     /// This should be called after adding children to a node to return
@@ -146,6 +168,33 @@ impl Default for TreeBuilder {
     }
 }
 
+/// Appends trees as children of the current node.
+///
+/// Each tree is added as a complete subtree: the builder's current context is
+/// unchanged, so subsequent calls like [`leaf`](TreeBuilder::leaf) still add to
+/// the same node rather than to an extended one. Trees are ignored if no node
+/// has been added yet.
+///
+/// # Examples
+///
+/// ```
+/// use treelog::Tree;
+/// use treelog::builder::TreeBuilder;
+///
+/// let mut builder = TreeBuilder::new();
+/// builder.node("root");
+/// builder.extend((1..=3).map(|i| Tree::new_leaf(format!("item {i}"))));
+/// let tree = builder.build();
+/// assert_eq!(tree.child_count(), Some(3));
+/// ```
+impl Extend<Tree> for TreeBuilder {
+    fn extend<I: IntoIterator<Item = Tree>>(&mut self, iter: I) {
+        if let Some(Tree::Node(_, children)) = self.stack.last_mut() {
+            children.extend(iter);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -189,4 +238,44 @@ mod tests {
             assert_eq!(children.len(), 3);
         }
     }
+
+    #[test]
+    fn test_reserve() {
+        let mut builder = TreeBuilder::new();
+        builder.node("root").reserve(10_000);
+        for i in 0..10_000 {
+            builder.leaf(i.to_string());
+        }
+        let tree = builder.build();
+
+        let children = tree.children().unwrap();
+        assert_eq!(children.len(), 10_000);
+        assert_eq!(children[0], Tree::new_leaf("0"));
+        assert_eq!(children[9_999], Tree::new_leaf("9999"));
+    }
+
+    #[test]
+    fn test_extend() {
+        let mut builder = TreeBuilder::new();
+        builder.node("root").node("child");
+        builder.extend(vec![
+            Tree::new_leaf("a"),
+            Tree::new_leaf("b"),
+            Tree::new_leaf("c"),
+        ]);
+        builder.leaf("d").end().leaf("e");
+        let tree = builder.build();
+
+        let child = &tree.children().unwrap()[0];
+        assert_eq!(
+            child.children().unwrap(),
+            &[
+                Tree::new_leaf("a"),
+                Tree::new_leaf("b"),
+                Tree::new_leaf("c"),
+                Tree::new_leaf("d"),
+            ]
+        );
+        assert_eq!(tree.child_count(), Some(2));
+    }
 }
@@ -42,15 +42,19 @@ pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
 
-    /// Tree style
-    #[arg(long, global = true, value_enum, default_value = "unicode")]
-    pub style: treelog::TreeStyle,
+    /// Render configuration file (TOML, JSON, YAML, or RON); flags override its values
+    #[arg(long, global = true)]
+    pub config: Option<String>,
+
+    /// Tree style [default: unicode]
+    #[arg(long, global = true, value_enum)]
+    pub style: Option<treelog::TreeStyle>,
 
     /// Custom style characters (format: branch,last,vertical,empty)
     #[arg(long, global = true)]
     pub custom_style: Option<String>,
 
-    /// Enable color output
+    /// Enable color output (by default, colors are used only when writing to a terminal)
     #[arg(long, global = true, action = ArgAction::SetTrue)]
     pub color: bool,
 
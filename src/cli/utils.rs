@@ -96,7 +96,10 @@ pub fn output_tree(tree: &treelog::Tree, cli: &Cli) -> Result<(), Box<dyn std::e
 pub fn build_render_config(cli: &Cli) -> Result<treelog::RenderConfig, Box<dyn std::error::Error>> {
     use treelog::{RenderConfig, StyleConfig};
 
-    let mut config = RenderConfig::default();
+    let mut config = match &cli.config {
+        Some(path) => load_render_config(path)?,
+        None => RenderConfig::default(),
+    };
 
     // Set style
     if let Some(custom) = &cli.custom_style {
@@ -113,10 +116,10 @@ pub fn build_render_config(cli: &Cli) -> Result<treelog::RenderConfig, Box<dyn s
             parts[2].trim(),
             parts[3].trim(),
         ));
-    } else {
+    } else if let Some(style) = &cli.style {
         // cli.style is already a treelog::TreeStyle (Unicode, Ascii, or Box)
         // Custom variant is skipped by ValueEnum, so we can safely use it
-        config = config.with_style(cli.style.clone());
+        config = config.with_style(style.clone());
     }
 
     // Set colors
@@ -126,8 +129,82 @@ pub fn build_render_config(cli: &Cli) -> Result<treelog::RenderConfig, Box<dyn s
             config = config.with_colors(true);
         } else if cli.no_color {
             config = config.with_colors(false);
+        } else if cli.config.is_none() {
+            let to_file = cli.output.as_deref().is_some_and(|path| path != "-");
+            config = if to_file {
+                config.with_colors(false)
+            } else {
+                config.auto_color()
+            };
         }
     }
 
     Ok(config)
 }
+
+/// Loads a serialized render configuration, choosing the format by file extension.
+#[allow(unused_variables)]
+pub fn load_render_config(path: &str) -> Result<treelog::RenderConfig, Box<dyn std::error::Error>> {
+    let content = read_file_or_stdin(path)?;
+    let extension = std::path::Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("");
+
+    match extension {
+        #[cfg(feature = "serde-toml")]
+        "toml" => Ok(toml::from_str(&content)?),
+        #[cfg(feature = "serde-json")]
+        "json" => Ok(serde_json::from_str(&content)?),
+        #[cfg(feature = "serde-yaml")]
+        "yaml" | "yml" => Ok(serde_yaml::from_str(&content)?),
+        #[cfg(feature = "serde-ron")]
+        "ron" => Ok(ron::from_str(&content)?),
+        _ => Err(format!(
+            "Unsupported config file '{}'. Use a .toml, .json, .yaml, or .ron file with the matching serde feature enabled.",
+            path
+        )
+        .into()),
+    }
+}
+
+#[cfg(all(test, feature = "serde-toml"))]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[test]
+    fn test_config_file_style() {
+        let path =
+            std::env::temp_dir().join(format!("treelog-cli-config-{}.toml", std::process::id()));
+        std::fs::write(
+            &path,
+            "[style]\nbranch = \">> \"\nlast = \"\\\\_ \"\nvertical = \"!  \"\nempty = \"   \"\n",
+        )
+        .unwrap();
+        let path_str = path.to_str().unwrap();
+
+        let cli = Cli::parse_from(["treelog", "--config", path_str, "render"]);
+        let config = build_render_config(&cli).unwrap();
+        let tree = treelog::Tree::Node(
+            "root".to_string(),
+            vec![
+                treelog::Tree::Leaf(vec!["a".to_string()]),
+                treelog::Tree::Leaf(vec!["b".to_string()]),
+            ],
+        );
+        assert_eq!(
+            tree.render_to_string_with_config(&config),
+            "root\n>> a\n\\_ b\n"
+        );
+
+        // Flags take precedence over the file
+        let cli = Cli::parse_from([
+            "treelog", "--config", path_str, "--style", "ascii", "render",
+        ]);
+        let config = build_render_config(&cli).unwrap();
+        assert_eq!(config.style.branch, "+- ");
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
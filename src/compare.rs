@@ -1,5 +1,12 @@
 //! Tree comparison operations.
 
+use std::collections::HashMap;
+#[cfg(feature = "color")]
+use std::collections::HashSet;
+use std::fmt;
+
+#[cfg(feature = "color")]
+use crate::config::RenderConfig;
 use crate::tree::Tree;
 
 /// Represents a difference between two trees.
@@ -17,6 +24,42 @@ pub enum TreeDiff {
     },
 }
 
+/// A single operation in an edit script produced by [`Tree::edit_script`].
+///
+/// Paths are child indices into the tree as it is while the script is applied
+/// in order, so each operation sees the effects of the ones before it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EditOp {
+    /// Change the label of the node at `path`, or the lines of the leaf at `path`
+    /// (joined with `\n`)
+    Relabel {
+        path: Vec<usize>,
+        from: String,
+        to: String,
+    },
+    /// Insert `subtree` so that it ends up at `path`
+    Insert { path: Vec<usize>, subtree: Tree },
+    /// Delete the subtree at `path`
+    Delete { path: Vec<usize>, subtree: Tree },
+}
+
+/// Error returned when two trees cannot be overlaid because their node skeletons differ.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MismatchError {
+    /// Child indices (in the first tree) of the element where the skeletons differ
+    pub path: Vec<usize>,
+    /// Description of the difference
+    pub reason: String,
+}
+
+impl fmt::Display for MismatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "trees differ at path {:?}: {}", self.path, self.reason)
+    }
+}
+
+impl std::error::Error for MismatchError {}
+
 impl Tree {
     /// Compares the structure of two trees, ignoring labels and content.
     ///
@@ -149,6 +192,300 @@ impl Tree {
         }
     }
 
+    /// Computes the differences between two trees, matching children by label.
+    ///
+    /// Unlike [`diff`](Self::diff), which aligns children by position, children are
+    /// matched by their label (or first line, for leaves) within each node, so
+    /// reordered but otherwise equal children are not reported. Repeated labels are
+    /// matched in order of appearance. Unmatched children are reported as
+    /// [`TreeDiff::OnlyInFirst`] or [`TreeDiff::OnlyInSecond`].
+    ///
+    /// Paths use child indices in the first tree, except that the last index of an
+    /// `OnlyInSecond` entry is the child's index in the second tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let tree1 = Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["a".to_string()]),
+    ///     Tree::Leaf(vec!["b".to_string()])
+    /// ]);
+    /// let tree2 = Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["b".to_string()]),
+    ///     Tree::Leaf(vec!["a".to_string()])
+    /// ]);
+    /// assert!(tree1.diff_by_label(&tree2).is_empty());
+    /// assert!(!tree1.diff(&tree2).is_empty());
+    /// ```
+    pub fn diff_by_label(&self, other: &Tree) -> Vec<TreeDiff> {
+        let mut diffs = Vec::new();
+        self.diff_by_label_recursive(other, &mut diffs, &mut Vec::new());
+        diffs
+    }
+
+    fn diff_by_label_recursive(
+        &self,
+        other: &Tree,
+        diffs: &mut Vec<TreeDiff>,
+        path: &mut Vec<usize>,
+    ) {
+        let (Tree::Node(label1, children1), Tree::Node(label2, children2)) = (self, other) else {
+            // Leaves and node/leaf pairs compare the same way regardless of alignment
+            self.diff_recursive(other, diffs, path);
+            return;
+        };
+
+        if label1 != label2 {
+            diffs.push(TreeDiff::DifferentContent {
+                path: path.clone(),
+                first: label1.clone(),
+                second: label2.clone(),
+            });
+        }
+
+        // Queue the indices of the second tree's children by label, in order
+        let mut unmatched: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (index, child) in children2.iter().enumerate().rev() {
+            unmatched.entry(child.diff_label()).or_default().push(index);
+        }
+
+        let mut matched = vec![false; children2.len()];
+        for (index, child1) in children1.iter().enumerate() {
+            path.push(index);
+            match unmatched.get_mut(child1.diff_label()).and_then(Vec::pop) {
+                Some(other_index) => {
+                    matched[other_index] = true;
+                    child1.diff_by_label_recursive(&children2[other_index], diffs, path);
+                }
+                None => diffs.push(TreeDiff::OnlyInFirst {
+                    path: path.clone(),
+                    content: child1.diff_label().to_string(),
+                }),
+            }
+            path.pop();
+        }
+
+        for (index, child2) in children2.iter().enumerate() {
+            if !matched[index] {
+                path.push(index);
+                diffs.push(TreeDiff::OnlyInSecond {
+                    path: path.clone(),
+                    content: child2.diff_label().to_string(),
+                });
+                path.pop();
+            }
+        }
+    }
+
+    /// Renders the tree with each line styled by how it differs from `baseline`.
+    ///
+    /// Elements are matched as in [`diff_by_label`](Self::diff_by_label). Lines of
+    /// elements missing from the baseline (and everything below them) are shown in
+    /// bold green, lines of elements whose content changed in yellow, and
+    /// unchanged lines dimmed, in place of the usual colors. Elements that only
+    /// exist in the baseline are not shown. When colors are disabled in `config`,
+    /// the output is the plain rendering.
+    ///
+    /// Requires the `color` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::{RenderConfig, Tree};
+    ///
+    /// let baseline = Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["a".to_string()])
+    /// ]);
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["a".to_string()]),
+    ///     Tree::Leaf(vec!["b".to_string()])
+    /// ]);
+    /// let config = RenderConfig::default().with_colors(true);
+    /// println!("{}", tree.render_against_baseline(&baseline, &config));
+    /// ```
+    #[cfg(feature = "color")]
+    pub fn render_against_baseline(&self, baseline: &Tree, config: &RenderConfig) -> String {
+        use colored::Colorize;
+
+        let mut added = HashSet::new();
+        let mut changed = HashSet::new();
+        for diff in self.diff_by_label(baseline) {
+            match diff {
+                TreeDiff::OnlyInFirst { path, .. } => {
+                    added.insert(path);
+                }
+                TreeDiff::DifferentContent { path, .. } => {
+                    // Below the root, children are matched by label, so a difference
+                    // on a node means the baseline has a leaf there instead
+                    let is_node = path
+                        .iter()
+                        .try_fold(self, |tree, &index| tree.children()?.get(index))
+                        .is_some_and(Tree::is_node);
+                    if is_node && (!path.is_empty() || baseline.is_leaf()) {
+                        added.insert(path);
+                    } else {
+                        changed.insert(path);
+                    }
+                }
+                TreeDiff::OnlyInSecond { .. } => {}
+            }
+        }
+
+        let restyle = |path: &[usize], text: String| {
+            if !config.colors {
+                text
+            } else if (0..=path.len()).any(|depth| added.contains(&path[..depth])) {
+                text.green().bold().to_string()
+            } else if changed.contains(path) {
+                text.yellow().to_string()
+            } else {
+                text.dimmed().to_string()
+            }
+        };
+        crate::renderer::render_to_string_restyled(self, &restyle, config)
+    }
+
+    /// Computes a minimal script of edits transforming this tree into `other`.
+    ///
+    /// Uses a top-down ordered tree edit distance: the roots are matched, and the
+    /// children of every matched pair of nodes are aligned by dynamic programming,
+    /// where each child is either matched with a child of the same kind (recursively),
+    /// deleted as a whole subtree, or inserted as a whole subtree. Every operation
+    /// costs one, and a script with the fewest operations is returned. If the roots
+    /// are of different kinds, the script deletes and re-inserts the root at the
+    /// empty path.
+    ///
+    /// Applying the operations in order to this tree yields `other`. Identical
+    /// trees produce an empty script.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    /// use treelog::compare::EditOp;
+    ///
+    /// let tree1 = Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["a".to_string()]),
+    ///     Tree::Leaf(vec!["b".to_string()])
+    /// ]);
+    /// let tree2 = Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["b".to_string()])
+    /// ]);
+    /// assert_eq!(tree1.edit_script(&tree2), vec![EditOp::Delete {
+    ///     path: vec![0],
+    ///     subtree: Tree::Leaf(vec!["a".to_string()]),
+    /// }]);
+    /// ```
+    pub fn edit_script(&self, other: &Tree) -> Vec<EditOp> {
+        match edit_script_between(self, other) {
+            Some(ops) => ops,
+            None => vec![
+                EditOp::Delete {
+                    path: Vec::new(),
+                    subtree: self.clone(),
+                },
+                EditOp::Insert {
+                    path: Vec::new(),
+                    subtree: other.clone(),
+                },
+            ],
+        }
+    }
+
+    /// Returns the structure shared by all of the given trees.
+    ///
+    /// The roots must align, and children are aligned by label: a node matches a
+    /// node with the same label and a leaf matches a leaf with identical lines.
+    /// Each child of the first tree is paired with the first unpaired matching
+    /// child in every other tree; if all trees have one, it is kept (with its own
+    /// common structure), otherwise it is dropped. Children keep the order of the
+    /// first tree. Returns `None` if `trees` is empty or the roots don't align.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let a = Tree::Node("config".to_string(), vec![
+    ///     Tree::Node("server".to_string(), vec![Tree::Leaf(vec!["port = 80".to_string()])]),
+    ///     Tree::Node("cache".to_string(), vec![])
+    /// ]);
+    /// let b = Tree::Node("config".to_string(), vec![
+    ///     Tree::Node("server".to_string(), vec![Tree::Leaf(vec!["port = 8080".to_string()])])
+    /// ]);
+    /// assert_eq!(
+    ///     Tree::common_ancestor_structure(&[a, b]),
+    ///     Some(Tree::Node("config".to_string(), vec![Tree::Node("server".to_string(), vec![])]))
+    /// );
+    /// ```
+    pub fn common_ancestor_structure(trees: &[Tree]) -> Option<Tree> {
+        let (first, rest) = trees.split_first()?;
+        let refs: Vec<&Tree> = rest.iter().collect();
+        first.common_structure(&refs)
+    }
+
+    fn common_structure(&self, others: &[&Tree]) -> Option<Tree> {
+        if !others.iter().all(|other| self.aligns_with(other)) {
+            return None;
+        }
+        let Tree::Node(label, children) = self else {
+            return Some(self.clone());
+        };
+        let other_children: Vec<&[Tree]> = others
+            .iter()
+            .map(|other| other.children().unwrap_or_default())
+            .collect();
+        let mut used: Vec<Vec<bool>> = other_children
+            .iter()
+            .map(|children| vec![false; children.len()])
+            .collect();
+        let mut common = Vec::new();
+        for child in children {
+            let matches: Option<Vec<usize>> = other_children
+                .iter()
+                .zip(used.iter())
+                .map(|(candidates, used)| {
+                    candidates
+                        .iter()
+                        .enumerate()
+                        .position(|(index, candidate)| !used[index] && child.aligns_with(candidate))
+                })
+                .collect();
+            let Some(matches) = matches else {
+                continue;
+            };
+            let matched: Vec<&Tree> = matches
+                .iter()
+                .zip(other_children.iter())
+                .map(|(&index, candidates)| &candidates[index])
+                .collect();
+            for (used, &index) in used.iter_mut().zip(matches.iter()) {
+                used[index] = true;
+            }
+            common.extend(child.common_structure(&matched));
+        }
+        Some(Tree::Node(label.clone(), common))
+    }
+
+    /// Checks whether two elements align for structure matching: nodes by label, leaves by lines.
+    fn aligns_with(&self, other: &Tree) -> bool {
+        match (self, other) {
+            (Tree::Node(a, _), Tree::Node(b, _)) => a == b,
+            (Tree::Leaf(a), Tree::Leaf(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    /// Returns the label of a node or the first line of a leaf.
+    fn diff_label(&self) -> &str {
+        match self {
+            Tree::Node(label, _) => label,
+            Tree::Leaf(lines) => lines.first().map(String::as_str).unwrap_or_default(),
+        }
+    }
+
     /// Checks if this tree is a subtree of another tree.
     ///
     /// Returns `true` if this tree structure and content appears as a
@@ -185,6 +522,340 @@ impl Tree {
 
         false
     }
+
+    /// Checks if this tree is a structural prefix of another tree.
+    ///
+    /// This tree must match the top portion of `other` position by position: nodes
+    /// must have equal labels and leaves equal lines. A childless node in this tree
+    /// matches a node with the same label and any children in `other`, so `other`
+    /// may continue deeper; otherwise both nodes must have the same number of
+    /// children. Unlike [`is_subtree_of`](Self::is_subtree_of), the match is
+    /// anchored at the root, and unlike [`eq_structure`](Self::eq_structure),
+    /// content is compared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let prefix = Tree::Node("root".to_string(), vec![
+    ///     Tree::Node("src".to_string(), vec![])
+    /// ]);
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Node("src".to_string(), vec![
+    ///         Tree::Leaf(vec!["main.rs".to_string()])
+    ///     ])
+    /// ]);
+    /// assert!(prefix.is_structural_prefix_of(&tree));
+    /// assert!(!tree.is_structural_prefix_of(&prefix));
+    /// ```
+    pub fn is_structural_prefix_of(&self, other: &Tree) -> bool {
+        match (self, other) {
+            (Tree::Node(l1, c1), Tree::Node(l2, c2)) => {
+                l1 == l2
+                    && (c1.is_empty()
+                        || (c1.len() == c2.len()
+                            && c1
+                                .iter()
+                                .zip(c2.iter())
+                                .all(|(a, b)| a.is_structural_prefix_of(b))))
+            }
+            (Tree::Leaf(a), Tree::Leaf(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    /// Compares two trees for equality, ignoring synthetic empty-label roots.
+    ///
+    /// A node with an empty label is treated as a wrapper around its children,
+    /// as produced by `IncrementalTree::build_tree` when there are multiple
+    /// roots. Both sides are unwrapped to their list of top-level trees before
+    /// comparing, so a single root equals the same root wrapped in an
+    /// empty-label node.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["a".to_string()])
+    /// ]);
+    /// let wrapped = Tree::Node(String::new(), vec![tree.clone()]);
+    /// assert!(tree.eq_ignoring_empty_roots(&wrapped));
+    /// assert_ne!(tree, wrapped);
+    /// ```
+    pub fn eq_ignoring_empty_roots(&self, other: &Tree) -> bool {
+        self.unwrap_empty_roots() == other.unwrap_empty_roots()
+    }
+
+    /// Overlays two trees with the same node skeleton, combining their leaves.
+    ///
+    /// The node skeletons (node labels and the order of node children, ignoring
+    /// leaves) must be equal. Each node in the result keeps the shared node children
+    /// in order, overlaid. Each run of leaves between two node children (or before
+    /// the first or after the last) stays at that position: first this tree's
+    /// leaves, with lines prefixed by `first: `, then `other`'s, prefixed by
+    /// `second: `. Overlaying two leaves gives a single leaf with both sets of lines.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`MismatchError`] if node labels differ, the number of node
+    /// children differs, or a node is compared with a leaf.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let before = Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["v1".to_string()])
+    /// ]);
+    /// let after = Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["v2".to_string()])
+    /// ]);
+    /// let overlay = before.overlay(&after).unwrap();
+    /// assert_eq!(overlay, Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["first: v1".to_string()]),
+    ///     Tree::Leaf(vec!["second: v2".to_string()])
+    /// ]));
+    /// ```
+    pub fn overlay(&self, other: &Tree) -> Result<Tree, MismatchError> {
+        self.overlay_recursive(other, &mut Vec::new())
+    }
+
+    fn overlay_recursive(
+        &self,
+        other: &Tree,
+        path: &mut Vec<usize>,
+    ) -> Result<Tree, MismatchError> {
+        let mismatch = |path: &[usize], reason: String| MismatchError {
+            path: path.to_vec(),
+            reason,
+        };
+        match (self, other) {
+            (Tree::Leaf(first), Tree::Leaf(second)) => {
+                let mut lines = labeled_lines(first, "first");
+                lines.extend(labeled_lines(second, "second"));
+                Ok(Tree::Leaf(lines))
+            }
+            (Tree::Node(first_label, first), Tree::Node(second_label, second)) => {
+                if first_label != second_label {
+                    return Err(mismatch(
+                        path,
+                        format!("labels differ: {first_label:?} vs {second_label:?}"),
+                    ));
+                }
+                let node_count =
+                    |children: &[Tree]| children.iter().filter(|c| c.is_node()).count();
+                let (first_nodes, second_nodes) = (node_count(first), node_count(second));
+                if first_nodes != second_nodes {
+                    return Err(mismatch(
+                        path,
+                        format!("node child counts differ: {first_nodes} vs {second_nodes}"),
+                    ));
+                }
+
+                let mut children = Vec::with_capacity(first.len() + second.len());
+                let (mut first_rest, mut second_rest) = (first.as_slice(), second.as_slice());
+                loop {
+                    // Leaves up to the next node child of each side
+                    let first_run = first_rest.iter().take_while(|c| c.is_leaf()).count();
+                    let second_run = second_rest.iter().take_while(|c| c.is_leaf()).count();
+                    for (source, leaves) in [
+                        ("first", &first_rest[..first_run]),
+                        ("second", &second_rest[..second_run]),
+                    ] {
+                        children.extend(leaves.iter().filter_map(|child| {
+                            child
+                                .lines()
+                                .map(|lines| Tree::Leaf(labeled_lines(lines, source)))
+                        }));
+                    }
+                    first_rest = &first_rest[first_run..];
+                    second_rest = &second_rest[second_run..];
+                    let (Some((first_child, first_after)), Some((second_child, second_after))) =
+                        (first_rest.split_first(), second_rest.split_first())
+                    else {
+                        break;
+                    };
+                    path.push(first.len() - first_rest.len());
+                    children.push(first_child.overlay_recursive(second_child, path)?);
+                    path.pop();
+                    first_rest = first_after;
+                    second_rest = second_after;
+                }
+                Ok(Tree::Node(first_label.clone(), children))
+            }
+            _ => Err(mismatch(path, "a node is compared with a leaf".to_string())),
+        }
+    }
+
+    fn unwrap_empty_roots(&self) -> &[Tree] {
+        match self {
+            Tree::Node(label, children) if label.is_empty() => {
+                if let [only] = children.as_slice() {
+                    only.unwrap_empty_roots()
+                } else {
+                    children
+                }
+            }
+            _ => std::slice::from_ref(self),
+        }
+    }
+}
+
+/// Alignment step for the children of two matched nodes.
+#[derive(Clone, Copy)]
+enum Align {
+    Match(usize, usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+/// Computes the cost of the cheapest script matching `a` with `b`.
+///
+/// Returns `None` if `a` and `b` are of different kinds and can't be matched.
+fn edit_cost(a: &Tree, b: &Tree) -> Option<usize> {
+    match (a, b) {
+        (Tree::Leaf(lines1), Tree::Leaf(lines2)) => Some(usize::from(lines1 != lines2)),
+        (Tree::Node(label1, children1), Tree::Node(label2, children2)) => {
+            let (_, cost) = alignment_costs(children1, children2);
+            Some(cost[children1.len()][children2.len()] + usize::from(label1 != label2))
+        }
+        _ => None,
+    }
+}
+
+/// Fills the alignment table for two lists of children.
+///
+/// Returns the cost of matching each pair of children, and the table where
+/// `cost[i][j]` is the cheapest alignment of the first `i` children with the first `j`.
+fn alignment_costs(
+    children1: &[Tree],
+    children2: &[Tree],
+) -> (Vec<Vec<Option<usize>>>, Vec<Vec<usize>>) {
+    let (n, m) = (children1.len(), children2.len());
+    let pairs: Vec<Vec<Option<usize>>> = children1
+        .iter()
+        .map(|c1| children2.iter().map(|c2| edit_cost(c1, c2)).collect())
+        .collect();
+
+    let mut cost = vec![vec![0; m + 1]; n + 1];
+    for (i, row) in cost.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in cost[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=n {
+        for j in 1..=m {
+            let mut best = (cost[i - 1][j] + 1).min(cost[i][j - 1] + 1);
+            if let Some(pair_cost) = pairs[i - 1][j - 1] {
+                best = best.min(cost[i - 1][j - 1] + pair_cost);
+            }
+            cost[i][j] = best;
+        }
+    }
+    (pairs, cost)
+}
+
+/// Computes the script (with paths relative to `a`) for matching `a` with `b`.
+///
+/// Only costs are compared while aligning children; operations are built for the
+/// chosen alignment alone. Returns `None` if `a` and `b` are of different kinds
+/// and can't be matched.
+fn edit_script_between(a: &Tree, b: &Tree) -> Option<Vec<EditOp>> {
+    match (a, b) {
+        (Tree::Leaf(lines1), Tree::Leaf(lines2)) => Some(if lines1 == lines2 {
+            Vec::new()
+        } else {
+            vec![EditOp::Relabel {
+                path: Vec::new(),
+                from: lines1.join("\n"),
+                to: lines2.join("\n"),
+            }]
+        }),
+        (Tree::Node(label1, children1), Tree::Node(label2, children2)) => {
+            let mut ops = Vec::new();
+            if label1 != label2 {
+                ops.push(EditOp::Relabel {
+                    path: Vec::new(),
+                    from: label1.clone(),
+                    to: label2.clone(),
+                });
+            }
+
+            let (pairs, cost) = alignment_costs(children1, children2);
+
+            // Walk back from the end to recover the alignment, preferring matches
+            let (n, m) = (children1.len(), children2.len());
+            let mut steps = Vec::with_capacity(n + m);
+            let (mut i, mut j) = (n, m);
+            while i > 0 || j > 0 {
+                let matched = i > 0
+                    && j > 0
+                    && pairs[i - 1][j - 1]
+                        .is_some_and(|pair_cost| cost[i][j] == cost[i - 1][j - 1] + pair_cost);
+                if matched {
+                    steps.push(Align::Match(i - 1, j - 1));
+                    i -= 1;
+                    j -= 1;
+                } else if i > 0 && cost[i][j] == cost[i - 1][j] + 1 {
+                    steps.push(Align::Delete(i - 1));
+                    i -= 1;
+                } else {
+                    steps.push(Align::Insert(j - 1));
+                    j -= 1;
+                }
+            }
+
+            // Emit left to right, tracking each child's index in the partially edited node
+            let mut index = 0;
+            for step in steps.into_iter().rev() {
+                match step {
+                    Align::Match(i, j) => {
+                        let child_ops =
+                            edit_script_between(&children1[i], &children2[j]).unwrap_or_default();
+                        ops.extend(child_ops.into_iter().map(|op| op.under_index(index)));
+                        index += 1;
+                    }
+                    Align::Delete(i) => ops.push(EditOp::Delete {
+                        path: vec![index],
+                        subtree: children1[i].clone(),
+                    }),
+                    Align::Insert(j) => {
+                        ops.push(EditOp::Insert {
+                            path: vec![index],
+                            subtree: children2[j].clone(),
+                        });
+                        index += 1;
+                    }
+                }
+            }
+            Some(ops)
+        }
+        _ => None,
+    }
+}
+
+impl EditOp {
+    /// Prefixes the operation's path with `index`.
+    fn under_index(mut self, index: usize) -> Self {
+        let (EditOp::Relabel { path, .. }
+        | EditOp::Insert { path, .. }
+        | EditOp::Delete { path, .. }) = &mut self;
+        path.insert(0, index);
+        self
+    }
+}
+
+/// Prefixes each line with the name of the tree it came from.
+fn labeled_lines(lines: &[String], source: &str) -> Vec<String> {
+    lines
+        .iter()
+        .map(|line| format!("{source}: {line}"))
+        .collect()
 }
 
 #[cfg(test)]
@@ -215,6 +886,200 @@ mod tests {
         assert!(!diffs.is_empty());
     }
 
+    #[test]
+    fn test_diff_by_label() {
+        let tree1 = Tree::Node(
+            "config".to_string(),
+            vec![
+                Tree::Node(
+                    "server".to_string(),
+                    vec![
+                        Tree::Leaf(vec!["host".to_string()]),
+                        Tree::Leaf(vec!["port".to_string()]),
+                    ],
+                ),
+                Tree::Leaf(vec!["name".to_string()]),
+                Tree::Leaf(vec!["debug".to_string()]),
+            ],
+        );
+        let reordered = Tree::Node(
+            "config".to_string(),
+            vec![
+                Tree::Leaf(vec!["debug".to_string()]),
+                Tree::Leaf(vec!["name".to_string()]),
+                Tree::Node(
+                    "server".to_string(),
+                    vec![
+                        Tree::Leaf(vec!["port".to_string()]),
+                        Tree::Leaf(vec!["host".to_string()]),
+                    ],
+                ),
+            ],
+        );
+        assert!(tree1.diff_by_label(&reordered).is_empty());
+        assert!(!tree1.diff(&reordered).is_empty());
+
+        let changed = Tree::Node(
+            "config".to_string(),
+            vec![
+                Tree::Leaf(vec!["verbose".to_string()]),
+                Tree::Node(
+                    "server".to_string(),
+                    vec![Tree::Leaf(vec!["port".to_string()])],
+                ),
+                Tree::Leaf(vec!["name".to_string()]),
+            ],
+        );
+        assert_eq!(
+            tree1.diff_by_label(&changed),
+            vec![
+                TreeDiff::OnlyInFirst {
+                    path: vec![0, 0],
+                    content: "host".to_string(),
+                },
+                TreeDiff::OnlyInFirst {
+                    path: vec![2],
+                    content: "debug".to_string(),
+                },
+                TreeDiff::OnlyInSecond {
+                    path: vec![0],
+                    content: "verbose".to_string(),
+                },
+            ]
+        );
+    }
+
+    fn leaf(text: &str) -> Tree {
+        Tree::Leaf(vec![text.to_string()])
+    }
+
+    fn node(label: &str, children: Vec<Tree>) -> Tree {
+        Tree::Node(label.to_string(), children)
+    }
+
+    /// Applies an edit script in order, following the documented path semantics.
+    fn apply(mut tree: Tree, ops: &[EditOp]) -> Tree {
+        fn parent<'a>(tree: &'a mut Tree, path: &[usize]) -> &'a mut Vec<Tree> {
+            let mut current = tree;
+            for &index in path {
+                current = &mut current.children_mut().unwrap()[index];
+            }
+            current.children_mut().unwrap()
+        }
+        for op in ops {
+            match op {
+                EditOp::Relabel { path, to, .. } => {
+                    let (last, rest) = match path.split_last() {
+                        Some((last, rest)) => (Some(*last), rest),
+                        None => (None, &[][..]),
+                    };
+                    let target = match last {
+                        Some(last) => &mut parent(&mut tree, rest)[last],
+                        None => &mut tree,
+                    };
+                    match target {
+                        Tree::Node(label, _) => *label = to.clone(),
+                        Tree::Leaf(lines) => *lines = to.split('\n').map(str::to_string).collect(),
+                    }
+                }
+                EditOp::Insert { path, subtree } => match path.split_last() {
+                    Some((&last, rest)) => parent(&mut tree, rest).insert(last, subtree.clone()),
+                    None => tree = subtree.clone(),
+                },
+                EditOp::Delete { path, .. } => {
+                    if let Some((&last, rest)) = path.split_last() {
+                        parent(&mut tree, rest).remove(last);
+                    }
+                }
+            }
+        }
+        tree
+    }
+
+    #[test]
+    fn test_edit_script_known_minimal() {
+        let tree = node("root", vec![leaf("a"), leaf("b"), leaf("c")]);
+        assert!(tree.edit_script(&tree).is_empty());
+
+        let deleted = node("root", vec![leaf("a"), leaf("c")]);
+        assert_eq!(
+            tree.edit_script(&deleted),
+            vec![EditOp::Delete {
+                path: vec![1],
+                subtree: leaf("b"),
+            }]
+        );
+
+        let relabeled = node("top", vec![leaf("a"), leaf("x"), leaf("c")]);
+        assert_eq!(
+            tree.edit_script(&relabeled),
+            vec![
+                EditOp::Relabel {
+                    path: vec![],
+                    from: "root".to_string(),
+                    to: "top".to_string(),
+                },
+                EditOp::Relabel {
+                    path: vec![1],
+                    from: "b".to_string(),
+                    to: "x".to_string(),
+                },
+            ]
+        );
+
+        // Relabel the root and one leaf, then insert one more
+        let grown = node("top", vec![leaf("a"), leaf("b"), leaf("x"), leaf("d")]);
+        let ops = tree.edit_script(&grown);
+        assert_eq!(ops.len(), 3);
+        assert_eq!(apply(tree.clone(), &ops), grown);
+
+        let nested = node("root", vec![node("dir", vec![leaf("a")]), leaf("c")]);
+        let nested_target = node(
+            "root",
+            vec![node("dir", vec![leaf("a"), leaf("b")]), leaf("c")],
+        );
+        assert_eq!(
+            nested.edit_script(&nested_target),
+            vec![EditOp::Insert {
+                path: vec![0, 1],
+                subtree: leaf("b"),
+            }]
+        );
+
+        let kind_change = node("root", vec![leaf("a")]);
+        let kind_target = node("root", vec![node("a", vec![])]);
+        assert_eq!(kind_change.edit_script(&kind_target).len(), 2);
+        assert_eq!(leaf("a").edit_script(&kind_target).len(), 2);
+    }
+
+    #[test]
+    fn test_edit_script_applies() {
+        let first = node(
+            "root",
+            vec![
+                node("src", vec![leaf("main.rs"), leaf("lib.rs")]),
+                leaf("README"),
+                node("tests", vec![leaf("a.rs")]),
+                leaf("LICENSE"),
+            ],
+        );
+        let second = node(
+            "project",
+            vec![
+                leaf("README"),
+                node("src", vec![leaf("lib.rs"), leaf("util.rs")]),
+                node("tests", vec![leaf("a.rs"), leaf("b.rs")]),
+                node("docs", vec![]),
+            ],
+        );
+        for (from, to) in [(&first, &second), (&second, &first)] {
+            let ops = from.edit_script(to);
+            assert_eq!(apply(from.clone(), &ops), *to);
+        }
+        // Relabel root, replace src as a whole (2), add b.rs, replace LICENSE with docs (2)
+        assert_eq!(first.edit_script(&second).len(), 6);
+    }
+
     #[test]
     fn test_is_subtree_of() {
         let subtree = Tree::Node(
@@ -233,4 +1098,260 @@ mod tests {
         );
         assert!(!not_subtree.is_subtree_of(&tree));
     }
+
+    #[test]
+    fn test_is_structural_prefix_of() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node("src".to_string(), vec![leaf("main.rs"), leaf("lib.rs")]),
+                leaf("README"),
+            ],
+        );
+        let prefix = Tree::Node(
+            "root".to_string(),
+            vec![Tree::Node("src".to_string(), vec![]), leaf("README")],
+        );
+        assert!(prefix.is_structural_prefix_of(&tree));
+        assert!(tree.is_structural_prefix_of(&tree));
+        assert!(Tree::Node("root".to_string(), vec![]).is_structural_prefix_of(&tree));
+        assert!(!tree.is_structural_prefix_of(&prefix));
+
+        // Not anchored anywhere below the root, unlike is_subtree_of
+        let inner = Tree::Node("src".to_string(), vec![]);
+        assert!(!inner.is_structural_prefix_of(&tree));
+        let src = Tree::Node("src".to_string(), vec![leaf("main.rs"), leaf("lib.rs")]);
+        assert!(src.is_subtree_of(&tree));
+        assert!(!src.is_structural_prefix_of(&tree));
+
+        // Content matters, unlike eq_structure
+        let renamed = Tree::Node(
+            "root".to_string(),
+            vec![Tree::Node("lib".to_string(), vec![]), leaf("README")],
+        );
+        assert!(!renamed.is_structural_prefix_of(&tree));
+        assert!(prefix.eq_structure(&renamed));
+
+        // Missing siblings are not a prefix
+        let partial = Tree::Node("root".to_string(), vec![leaf("README")]);
+        assert!(!partial.is_structural_prefix_of(&tree));
+    }
+
+    #[test]
+    fn test_common_ancestor_structure() {
+        let trees = vec![
+            node(
+                "config",
+                vec![
+                    node(
+                        "server",
+                        vec![leaf("host = a"), node("tls", vec![leaf("on")])],
+                    ),
+                    node("logging", vec![leaf("level = debug")]),
+                    leaf("version = 1"),
+                ],
+            ),
+            node(
+                "config",
+                vec![
+                    leaf("version = 1"),
+                    node("logging", vec![]),
+                    node(
+                        "server",
+                        vec![node("tls", vec![leaf("on")]), leaf("host = b")],
+                    ),
+                ],
+            ),
+            node(
+                "config",
+                vec![
+                    node("server", vec![node("tls", vec![leaf("off"), leaf("on")])]),
+                    node("logging", vec![leaf("level = info")]),
+                    node("cache", vec![]),
+                    leaf("version = 1"),
+                ],
+            ),
+        ];
+        assert_eq!(
+            Tree::common_ancestor_structure(&trees),
+            Some(node(
+                "config",
+                vec![
+                    node("server", vec![node("tls", vec![leaf("on")])]),
+                    node("logging", vec![]),
+                    leaf("version = 1"),
+                ],
+            ))
+        );
+
+        assert_eq!(
+            Tree::common_ancestor_structure(&trees[..1]),
+            Some(trees[0].clone())
+        );
+        assert_eq!(Tree::common_ancestor_structure(&[]), None);
+        assert_eq!(
+            Tree::common_ancestor_structure(&[trees[0].clone(), node("other", vec![])]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_eq_ignoring_empty_roots() {
+        let a = Tree::Node("a".to_string(), vec![Tree::Leaf(vec!["1".to_string()])]);
+        let b = Tree::Leaf(vec!["b".to_string()]);
+
+        let wrapped = Tree::Node(String::new(), vec![a.clone()]);
+        assert!(a.eq_ignoring_empty_roots(&wrapped));
+        assert!(wrapped.eq_ignoring_empty_roots(&a));
+
+        let nested = Tree::Node(String::new(), vec![wrapped.clone()]);
+        assert!(nested.eq_ignoring_empty_roots(&a));
+
+        let multi = Tree::Node(String::new(), vec![a.clone(), b.clone()]);
+        let multi_nested = Tree::Node(
+            String::new(),
+            vec![Tree::Node(String::new(), vec![a.clone(), b])],
+        );
+        assert!(multi.eq_ignoring_empty_roots(&multi_nested));
+        assert!(!multi.eq_ignoring_empty_roots(&a));
+
+        let other = Tree::Node("a".to_string(), vec![Tree::Leaf(vec!["2".to_string()])]);
+        assert!(!wrapped.eq_ignoring_empty_roots(&other));
+    }
+
+    #[test]
+    fn test_overlay() {
+        let first = Tree::Node(
+            "config".to_string(),
+            vec![
+                Tree::Leaf(vec!["name = a".to_string()]),
+                Tree::Node(
+                    "server".to_string(),
+                    vec![Tree::Leaf(vec!["port = 80".to_string()])],
+                ),
+            ],
+        );
+        let second = Tree::Node(
+            "config".to_string(),
+            vec![
+                Tree::Node(
+                    "server".to_string(),
+                    vec![
+                        Tree::Leaf(vec!["port = 8080".to_string()]),
+                        Tree::Leaf(vec!["tls = true".to_string()]),
+                    ],
+                ),
+                Tree::Leaf(vec!["name = b".to_string()]),
+            ],
+        );
+
+        let overlay = first.overlay(&second).unwrap();
+        assert_eq!(
+            overlay,
+            Tree::Node(
+                "config".to_string(),
+                vec![
+                    Tree::Leaf(vec!["first: name = a".to_string()]),
+                    Tree::Node(
+                        "server".to_string(),
+                        vec![
+                            Tree::Leaf(vec!["first: port = 80".to_string()]),
+                            Tree::Leaf(vec!["second: port = 8080".to_string()]),
+                            Tree::Leaf(vec!["second: tls = true".to_string()]),
+                        ],
+                    ),
+                    Tree::Leaf(vec!["second: name = b".to_string()]),
+                ],
+            )
+        );
+
+        let renamed = Tree::Node(
+            "config".to_string(),
+            vec![Tree::Node("client".to_string(), vec![])],
+        );
+        let err = first.overlay(&renamed).unwrap_err();
+        assert_eq!(err.path, vec![1]);
+        assert!(err.to_string().contains("labels differ"));
+
+        let extra = Tree::Node("config".to_string(), vec![]);
+        assert!(first.overlay(&extra).is_err());
+    }
+
+    #[test]
+    fn test_overlay_keeps_leaf_positions() {
+        let side = |name: &str, leaf: &str| {
+            Tree::Node(
+                "root".to_string(),
+                vec![
+                    Tree::Node("a".to_string(), vec![]),
+                    Tree::Leaf(vec![leaf.to_string()]),
+                    Tree::Node("b".to_string(), vec![Tree::Node(name.to_string(), vec![])]),
+                ],
+            )
+        };
+        let first = side("c", "x1");
+        let second = side("c", "x2");
+        assert_eq!(
+            first.overlay(&second).unwrap(),
+            Tree::Node(
+                "root".to_string(),
+                vec![
+                    Tree::Node("a".to_string(), vec![]),
+                    Tree::Leaf(vec!["first: x1".to_string()]),
+                    Tree::Leaf(vec!["second: x2".to_string()]),
+                    Tree::Node("b".to_string(), vec![Tree::Node("c".to_string(), vec![])]),
+                ],
+            )
+        );
+
+        // The path points into the first tree
+        let err = first.overlay(&side("d", "x2")).unwrap_err();
+        assert_eq!(err.path, vec![2, 0]);
+    }
+
+    #[cfg(feature = "color")]
+    #[test]
+    fn test_render_against_baseline() {
+        use crate::config::RenderConfig;
+
+        colored::control::set_override(true);
+        let baseline = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Leaf(vec!["same".to_string()]),
+                Tree::Leaf(vec!["edited".to_string(), "stale".to_string()]),
+                Tree::Leaf(vec!["removed".to_string()]),
+            ],
+        );
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Leaf(vec!["same".to_string()]),
+                Tree::Node(
+                    "new dir".to_string(),
+                    vec![Tree::Leaf(vec!["new file".to_string()])],
+                ),
+                Tree::Leaf(vec!["edited".to_string(), "fresh".to_string()]),
+            ],
+        );
+        let config = RenderConfig::default().with_colors(true);
+        let output = tree.render_against_baseline(&baseline, &config);
+        let line = |text: &str| output.lines().find(|l| l.contains(text)).unwrap();
+        let (added, changed, dimmed) = ("\x1b[1;32m", "\x1b[33m", "\x1b[2m");
+
+        assert!(line("new dir").contains(added));
+        assert!(line("new file").contains(added));
+        assert!(line("edited").contains(changed));
+        assert!(line("fresh").contains(changed));
+        assert!(line("root").contains(dimmed));
+        assert!(line("same").contains(dimmed));
+        assert!(!line("same").contains(added));
+        assert!(!output.contains("removed"));
+
+        let plain = RenderConfig::default();
+        assert_eq!(
+            tree.render_against_baseline(&baseline, &plain),
+            tree.render_to_string()
+        );
+    }
 }
@@ -1,6 +1,10 @@
 //! Configuration options for tree rendering.
 
+use std::collections::HashSet;
+use std::sync::Arc;
+
 use crate::style::StyleConfig;
+use crate::tree::Tree;
 
 /// Type alias for node formatter functions.
 #[cfg(feature = "formatters")]
@@ -10,6 +14,82 @@ type NodeFormatter = Box<dyn Fn(&str) -> String + Send + Sync>;
 #[cfg(feature = "formatters")]
 type LeafFormatter = Box<dyn Fn(&str) -> String + Send + Sync>;
 
+/// Type alias for functions producing the summary text for hidden items.
+type SummaryFn = Arc<dyn Fn(usize) -> String + Send + Sync>;
+
+/// Type alias for functions mapping leaf lines to hyperlink URLs.
+type HyperlinkFn = Arc<dyn Fn(&str) -> Option<String> + Send + Sync>;
+
+/// Type alias for functions classifying leaf lines by severity.
+type SeverityClassifier = Arc<dyn Fn(&str) -> Severity + Send + Sync>;
+
+/// Type alias for functions overriding the branch glyph of individual elements.
+type GlyphOverrideFn = Arc<dyn Fn(&[usize], &Tree) -> Option<String> + Send + Sync>;
+
+/// Severity of a leaf line, used to choose its color when colors are enabled.
+///
+/// Leaf lines classified as [`Severity::Normal`] keep the default leaf color.
+///
+/// # Examples
+///
+/// ```
+/// use treelog::{RenderConfig, Severity};
+///
+/// let config = RenderConfig::default()
+///     .with_colors(true)
+///     .with_severity_classifier(|line| {
+///         if line.contains("ERROR") {
+///             Severity::Error
+///         } else {
+///             Severity::Normal
+///         }
+///     });
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Severity {
+    /// Errors, rendered in red
+    Error,
+    /// Warnings, rendered in yellow
+    Warning,
+    /// Informational lines, rendered in cyan
+    Info,
+    /// Regular lines, rendered in the default leaf color
+    #[default]
+    Normal,
+}
+
+/// What the count shown next to node labels counts.
+///
+/// Used with [`RenderConfig::with_child_counts`].
+///
+/// # Examples
+///
+/// ```
+/// use treelog::{CountMode, RenderConfig};
+///
+/// let config = RenderConfig::default()
+///     .with_child_counts(true)
+///     .with_count_mode(CountMode::Descendants);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    any(
+        feature = "serde",
+        feature = "serde-json",
+        feature = "serde-yaml",
+        feature = "serde-toml",
+        feature = "serde-ron"
+    ),
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum CountMode {
+    /// Count the node's direct children
+    #[default]
+    Children,
+    /// Count all nodes and leaves below the node
+    Descendants,
+}
+
 /// Configuration for rendering a tree.
 ///
 /// This struct allows fine-grained control over how trees are rendered,
@@ -24,6 +104,21 @@ type LeafFormatter = Box<dyn Fn(&str) -> String + Send + Sync>;
 ///     .with_style(TreeStyle::Ascii)
 ///     .with_colors(false);
 /// ```
+///
+/// With a `serde-*` feature enabled, the configuration can be serialized and
+/// deserialized. Custom formatters cannot be serialized and are skipped, and
+/// missing fields fall back to their defaults.
+#[cfg_attr(
+    any(
+        feature = "serde",
+        feature = "serde-json",
+        feature = "serde-yaml",
+        feature = "serde-toml",
+        feature = "serde-ron"
+    ),
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct RenderConfig {
     /// Style configuration for the tree
     pub style: StyleConfig,
@@ -31,12 +126,108 @@ pub struct RenderConfig {
     pub colors: bool,
     /// Custom formatter function for node labels (requires `formatters` feature)
     #[cfg(feature = "formatters")]
+    #[cfg_attr(
+        any(
+            feature = "serde",
+            feature = "serde-json",
+            feature = "serde-yaml",
+            feature = "serde-toml",
+            feature = "serde-ron"
+        ),
+        serde(skip)
+    )]
     pub node_formatter: Option<NodeFormatter>,
     /// Custom formatter function for leaf lines (requires `formatters` feature)
     #[cfg(feature = "formatters")]
+    #[cfg_attr(
+        any(
+            feature = "serde",
+            feature = "serde-json",
+            feature = "serde-yaml",
+            feature = "serde-toml",
+            feature = "serde-ron"
+        ),
+        serde(skip)
+    )]
     pub leaf_formatter: Option<LeafFormatter>,
     /// Line ending character(s)
     pub line_ending: String,
+    /// Whether to strip trailing whitespace from each rendered line
+    pub trim_trailing: bool,
+    /// Paths (child indices from the root) of nodes whose subtrees are hidden
+    pub collapsed: HashSet<Vec<usize>>,
+    /// Number of child labels to preview inline on collapsed nodes
+    pub collapsed_preview: Option<usize>,
+    /// Custom summary text for hidden items, given the number of hidden items
+    #[cfg_attr(
+        any(
+            feature = "serde",
+            feature = "serde-json",
+            feature = "serde-yaml",
+            feature = "serde-toml",
+            feature = "serde-ron"
+        ),
+        serde(skip)
+    )]
+    pub summary_fn: Option<SummaryFn>,
+    /// Delimiter on which to column-align the lines of sibling leaves
+    pub align_on: Option<char>,
+    /// Function returning a URL for a leaf line, rendered as an OSC 8 hyperlink
+    #[cfg_attr(
+        any(
+            feature = "serde",
+            feature = "serde-json",
+            feature = "serde-yaml",
+            feature = "serde-toml",
+            feature = "serde-ron"
+        ),
+        serde(skip)
+    )]
+    pub hyperlink_fn: Option<HyperlinkFn>,
+    /// Whether to insert a blank line between the root's child subtrees
+    pub blank_line_between_top_level: bool,
+    /// Function classifying leaf lines by severity for coloring (requires `color` feature)
+    #[cfg_attr(
+        any(
+            feature = "serde",
+            feature = "serde-json",
+            feature = "serde-yaml",
+            feature = "serde-toml",
+            feature = "serde-ron"
+        ),
+        serde(skip)
+    )]
+    pub severity_classifier: Option<SeverityClassifier>,
+    /// Whether to append ` (N)` child counts to node labels
+    pub show_child_counts: bool,
+    /// What the child counts count
+    pub count_mode: CountMode,
+    /// Whether to pad prefixes so all labels start at the same column
+    pub align_labels: bool,
+    /// Number of columns between tab stops when measuring or expanding tabs
+    pub tab_width: usize,
+    /// Whether to replace tabs in labels and leaf lines with spaces
+    pub expand_tabs: bool,
+    /// Function returning a glyph that replaces the branch glyph of an element's first line
+    #[cfg_attr(
+        any(
+            feature = "serde",
+            feature = "serde-json",
+            feature = "serde-yaml",
+            feature = "serde-toml",
+            feature = "serde-ron"
+        ),
+        serde(skip)
+    )]
+    pub glyph_override_fn: Option<GlyphOverrideFn>,
+    /// Number of spaces between an element's line and its annotation
+    pub annotation_gutter: usize,
+    /// Bullet written before the first line of each leaf, with later lines indented to match
+    pub leaf_bullet: Option<String>,
+    /// Whether nodes without children get leaf formatting and colors instead of node ones
+    pub empty_nodes_as_leaves: bool,
+    /// Separator joining the children of a node into one line when they are all single-line leaves
+    pub join_leaf_siblings: Option<String>,
 }
 
 impl Clone for RenderConfig {
@@ -49,6 +240,24 @@ impl Clone for RenderConfig {
             #[cfg(feature = "formatters")]
             leaf_formatter: None, // Cannot clone function pointers, reset to None
             line_ending: self.line_ending.clone(),
+            trim_trailing: self.trim_trailing,
+            collapsed: self.collapsed.clone(),
+            collapsed_preview: self.collapsed_preview,
+            summary_fn: self.summary_fn.clone(),
+            align_on: self.align_on,
+            hyperlink_fn: self.hyperlink_fn.clone(),
+            blank_line_between_top_level: self.blank_line_between_top_level,
+            severity_classifier: self.severity_classifier.clone(),
+            show_child_counts: self.show_child_counts,
+            count_mode: self.count_mode,
+            align_labels: self.align_labels,
+            tab_width: self.tab_width,
+            expand_tabs: self.expand_tabs,
+            glyph_override_fn: self.glyph_override_fn.clone(),
+            annotation_gutter: self.annotation_gutter,
+            leaf_bullet: self.leaf_bullet.clone(),
+            empty_nodes_as_leaves: self.empty_nodes_as_leaves,
+            join_leaf_siblings: self.join_leaf_siblings.clone(),
         }
     }
 }
@@ -65,7 +274,30 @@ impl std::fmt::Debug for RenderConfig {
                 .field("node_formatter", &self.node_formatter.is_some())
                 .field("leaf_formatter", &self.leaf_formatter.is_some());
         }
-        debug.field("line_ending", &self.line_ending).finish()
+        debug
+            .field("line_ending", &self.line_ending)
+            .field("trim_trailing", &self.trim_trailing)
+            .field("collapsed", &self.collapsed)
+            .field("collapsed_preview", &self.collapsed_preview)
+            .field("summary_fn", &self.summary_fn.is_some())
+            .field("align_on", &self.align_on)
+            .field("hyperlink_fn", &self.hyperlink_fn.is_some())
+            .field(
+                "blank_line_between_top_level",
+                &self.blank_line_between_top_level,
+            )
+            .field("severity_classifier", &self.severity_classifier.is_some())
+            .field("show_child_counts", &self.show_child_counts)
+            .field("count_mode", &self.count_mode)
+            .field("align_labels", &self.align_labels)
+            .field("tab_width", &self.tab_width)
+            .field("expand_tabs", &self.expand_tabs)
+            .field("glyph_override_fn", &self.glyph_override_fn.is_some())
+            .field("annotation_gutter", &self.annotation_gutter)
+            .field("leaf_bullet", &self.leaf_bullet)
+            .field("empty_nodes_as_leaves", &self.empty_nodes_as_leaves)
+            .field("join_leaf_siblings", &self.join_leaf_siblings)
+            .finish()
     }
 }
 
@@ -79,6 +311,24 @@ impl Default for RenderConfig {
             #[cfg(feature = "formatters")]
             leaf_formatter: None,
             line_ending: "\n".to_string(),
+            trim_trailing: false,
+            collapsed: HashSet::new(),
+            collapsed_preview: None,
+            summary_fn: None,
+            align_on: None,
+            hyperlink_fn: None,
+            blank_line_between_top_level: false,
+            severity_classifier: None,
+            show_child_counts: false,
+            count_mode: CountMode::default(),
+            align_labels: false,
+            tab_width: 4,
+            expand_tabs: false,
+            glyph_override_fn: None,
+            annotation_gutter: 2,
+            leaf_bullet: None,
+            empty_nodes_as_leaves: false,
+            join_leaf_siblings: None,
         }
     }
 }
@@ -118,6 +368,42 @@ impl RenderConfig {
         self
     }
 
+    /// Enables colors only if stdout is a terminal and `NO_COLOR` is not set.
+    ///
+    /// Output piped to a file or a pager such as `less` is then rendered without
+    /// escape codes. Following <https://no-color.org>, a non-empty `NO_COLOR`
+    /// environment variable disables colors even on a terminal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::RenderConfig;
+    ///
+    /// let config = RenderConfig::default().auto_color();
+    /// ```
+    pub fn auto_color(self) -> Self {
+        use std::io::IsTerminal;
+
+        self.auto_color_for(std::io::stdout().is_terminal())
+    }
+
+    /// Like [`auto_color`](Self::auto_color), but for output to a stream whose
+    /// terminal status is already known.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::IsTerminal;
+    /// use treelog::RenderConfig;
+    ///
+    /// let config = RenderConfig::default().auto_color_for(std::io::stderr().is_terminal());
+    /// assert!(!RenderConfig::default().auto_color_for(false).colors);
+    /// ```
+    pub fn auto_color_for(self, is_terminal: bool) -> Self {
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        self.with_colors(is_terminal && !no_color)
+    }
+
     /// Sets a custom formatter for node labels.
     ///
     /// Requires the `formatters` feature.
@@ -174,6 +460,408 @@ impl RenderConfig {
         self
     }
 
+    /// Sets whether trailing whitespace is stripped from each rendered line.
+    ///
+    /// The built-in styles end their glyphs with a space (`├─ `), which leaves
+    /// trailing whitespace on lines with empty content. Only the end of the line
+    /// is trimmed, so prefixes and content alignment are unaffected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::RenderConfig;
+    ///
+    /// let config = RenderConfig::default().with_trim_trailing(true);
+    /// ```
+    pub fn with_trim_trailing(mut self, trim: bool) -> Self {
+        self.trim_trailing = trim;
+        self
+    }
+
+    /// Collapses the node at the given path so its subtree is not rendered.
+    ///
+    /// The path is a sequence of child indices from the root (the root itself is
+    /// the empty path). A collapsed node is rendered as its label followed by a
+    /// summary line counting the hidden children, or with an inline preview if
+    /// [`with_collapsed_preview`](Self::with_collapsed_preview) is set. Collapsing
+    /// a leaf or a childless node has no effect.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::RenderConfig;
+    ///
+    /// let config = RenderConfig::default().with_collapsed(vec![0, 1]);
+    /// ```
+    pub fn with_collapsed(mut self, path: impl Into<Vec<usize>>) -> Self {
+        self.collapsed.insert(path.into());
+        self
+    }
+
+    /// Shows up to `count` child labels inline on collapsed nodes, like `node [a, b, …]`.
+    ///
+    /// Nodes render their label and leaves their first line. An ellipsis is added
+    /// when the node has more children than are previewed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::RenderConfig;
+    ///
+    /// let config = RenderConfig::default()
+    ///     .with_collapsed(vec![0])
+    ///     .with_collapsed_preview(2);
+    /// ```
+    pub fn with_collapsed_preview(mut self, count: usize) -> Self {
+        self.collapsed_preview = Some(count);
+        self
+    }
+
+    /// Sets the function producing the summary line for hidden items.
+    ///
+    /// The function receives the number of hidden items. It is used wherever
+    /// rendering replaces content with a summary, such as collapsed nodes.
+    /// Defaults to `… (N more)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::RenderConfig;
+    ///
+    /// let config = RenderConfig::default().with_summary_fn(|n| format!("+{n} hidden"));
+    /// ```
+    pub fn with_summary_fn<F>(mut self, summary: F) -> Self
+    where
+        F: Fn(usize) -> String + Send + Sync + 'static,
+    {
+        self.summary_fn = Some(Arc::new(summary));
+        self
+    }
+
+    /// Aligns sibling leaf lines on the given delimiter, like `key: value` blocks.
+    ///
+    /// Within each node, the part of every leaf line before the first occurrence
+    /// of `delimiter` is padded to the widest such part among its sibling leaves,
+    /// so the delimiters and values line up. Lines without the delimiter are left
+    /// unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::RenderConfig;
+    ///
+    /// let config = RenderConfig::default().with_align_on(':');
+    /// ```
+    pub fn with_align_on(mut self, delimiter: char) -> Self {
+        self.align_on = Some(delimiter);
+        self
+    }
+
+    /// Sets a function that links leaf lines to URLs.
+    ///
+    /// The function receives each raw leaf line. When it returns `Some(url)`, the
+    /// rendered line content is wrapped in an OSC 8 escape sequence so supporting
+    /// terminals make it clickable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::RenderConfig;
+    ///
+    /// let config = RenderConfig::default()
+    ///     .with_hyperlink_fn(|line| line.ends_with(".rs").then(|| format!("file:///src/{line}")));
+    /// ```
+    pub fn with_hyperlink_fn<F>(mut self, hyperlink: F) -> Self
+    where
+        F: Fn(&str) -> Option<String> + Send + Sync + 'static,
+    {
+        self.hyperlink_fn = Some(Arc::new(hyperlink));
+        self
+    }
+
+    /// Inserts a blank line between the subtrees of the root's children.
+    ///
+    /// Only top-level subtrees are separated; nested children are rendered as usual.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::RenderConfig;
+    ///
+    /// let config = RenderConfig::default().with_blank_line_between_top_level(true);
+    /// ```
+    pub fn with_blank_line_between_top_level(mut self, blank_line: bool) -> Self {
+        self.blank_line_between_top_level = blank_line;
+        self
+    }
+
+    /// Sets a function that classifies leaf lines by severity.
+    ///
+    /// When colors are enabled, each leaf line is colored according to its
+    /// [`Severity`] instead of the default leaf color. Has no visible effect
+    /// without the `color` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::{RenderConfig, Severity};
+    ///
+    /// let config = RenderConfig::default()
+    ///     .with_colors(true)
+    ///     .with_severity_classifier(|line| match line {
+    ///         l if l.starts_with("ERROR") => Severity::Error,
+    ///         l if l.starts_with("WARN") => Severity::Warning,
+    ///         l if l.starts_with("INFO") => Severity::Info,
+    ///         _ => Severity::Normal,
+    ///     });
+    /// ```
+    pub fn with_severity_classifier<F>(mut self, classifier: F) -> Self
+    where
+        F: Fn(&str) -> Severity + Send + Sync + 'static,
+    {
+        self.severity_classifier = Some(Arc::new(classifier));
+        self
+    }
+
+    /// Appends a ` (N)` count to every node label.
+    ///
+    /// By default `N` is the number of direct children; use
+    /// [`with_count_mode`](Self::with_count_mode) to count all descendants instead.
+    /// Leaves get no count.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::{RenderConfig, Tree};
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["a".to_string()]),
+    ///     Tree::Leaf(vec!["b".to_string()])
+    /// ]);
+    /// let config = RenderConfig::default().with_child_counts(true);
+    /// assert_eq!(tree.render_to_string_with_config(&config), "root (2)\n├─ a\n└─ b\n");
+    /// ```
+    pub fn with_child_counts(mut self, show: bool) -> Self {
+        self.show_child_counts = show;
+        self
+    }
+
+    /// Sets what the counts shown by [`with_child_counts`](Self::with_child_counts) count.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::{CountMode, RenderConfig};
+    ///
+    /// let config = RenderConfig::default()
+    ///     .with_child_counts(true)
+    ///     .with_count_mode(CountMode::Descendants);
+    /// ```
+    pub fn with_count_mode(mut self, mode: CountMode) -> Self {
+        self.count_mode = mode;
+        self
+    }
+
+    /// Pads line prefixes so that every label starts at the same column.
+    ///
+    /// The column is the deepest rendered level times the width of the branch
+    /// glyph. Branch glyphs are extended with their horizontal stroke to reach
+    /// it, and the root and continuation lines are padded with spaces.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::{RenderConfig, Tree};
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["item".to_string()])
+    /// ]);
+    /// let config = RenderConfig::default().with_align_labels(true);
+    /// assert_eq!(tree.render_to_string_with_config(&config), "   root\n└─ item\n");
+    /// ```
+    pub fn with_align_labels(mut self, align: bool) -> Self {
+        self.align_labels = align;
+        self
+    }
+
+    /// Sets the number of columns between tab stops (default 4).
+    ///
+    /// Tabs are measured with this width when aligning with
+    /// [`with_align_on`](Self::with_align_on), and expanded with it when
+    /// [`with_expand_tabs`](Self::with_expand_tabs) is enabled. Tab stops are
+    /// counted from the start of the rendered line, including the tree prefix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::RenderConfig;
+    ///
+    /// let config = RenderConfig::default().with_tab_width(8);
+    /// ```
+    pub fn with_tab_width(mut self, tab_width: usize) -> Self {
+        self.tab_width = tab_width;
+        self
+    }
+
+    /// Replaces tabs in labels and leaf lines with spaces when rendering.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::{RenderConfig, Tree};
+    ///
+    /// let tree = Tree::Leaf(vec!["a\tb".to_string()]);
+    /// let config = RenderConfig::default().with_expand_tabs(true);
+    /// assert_eq!(tree.render_to_string_with_config(&config), "a   b\n");
+    /// ```
+    pub fn with_expand_tabs(mut self, expand: bool) -> Self {
+        self.expand_tabs = expand;
+        self
+    }
+
+    /// Sets a function that overrides the branch glyph of individual elements.
+    ///
+    /// The function receives the element's child-index path from the root and the
+    /// element itself. When it returns `Some(glyph)`, the glyph replaces the
+    /// branch or last-branch glyph in front of the element's first line; the
+    /// glyphs of its children and continuation lines are unchanged. The root has
+    /// no branch glyph and is never overridden.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::{RenderConfig, Tree};
+    ///
+    /// let config = RenderConfig::default().with_glyph_override_fn(|_path, tree| {
+    ///     tree.lines()
+    ///         .is_some_and(|lines| lines[0].starts_with("[x]"))
+    ///         .then(|| "✔─ ".to_string())
+    /// });
+    /// ```
+    pub fn with_glyph_override_fn<F>(mut self, glyph_override: F) -> Self
+    where
+        F: Fn(&[usize], &Tree) -> Option<String> + Send + Sync + 'static,
+    {
+        self.glyph_override_fn = Some(Arc::new(glyph_override));
+        self
+    }
+
+    /// Returns the glyph overriding the branch glyph of the element at `path`, if any.
+    pub(crate) fn glyph_override(&self, path: &[usize], tree: &Tree) -> Option<String> {
+        self.glyph_override_fn
+            .as_ref()
+            .and_then(|glyph_override| glyph_override(path, tree))
+    }
+
+    /// Sets the number of spaces between an element's line and its annotation (default 2).
+    ///
+    /// Annotations are supplied to [`Tree::render_with_annotations`](crate::Tree::render_with_annotations).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use treelog::{RenderConfig, Tree};
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![]);
+    /// let annotations = HashMap::from([(vec![], "# note".to_string())]);
+    /// let config = RenderConfig::default().with_annotation_gutter(4);
+    /// assert_eq!(tree.render_with_annotations(&annotations, &config), "root    # note\n");
+    /// ```
+    pub fn with_annotation_gutter(mut self, gutter: usize) -> Self {
+        self.annotation_gutter = gutter;
+        self
+    }
+
+    /// Sets a bullet written before the first line of each leaf, such as `"• "` or `"- "`.
+    ///
+    /// The bullet goes between the branch prefix and the leaf content; later lines
+    /// of a multi-line leaf are indented by the bullet's width. Node labels are unaffected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::{RenderConfig, Tree};
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![Tree::Leaf(vec!["item".to_string()])]);
+    /// let config = RenderConfig::default().with_leaf_bullet("• ");
+    /// assert_eq!(tree.render_to_string_with_config(&config), "root\n└─ • item\n");
+    /// ```
+    pub fn with_leaf_bullet(mut self, bullet: impl Into<String>) -> Self {
+        self.leaf_bullet = Some(bullet.into());
+        self
+    }
+
+    /// Sets whether nodes without children are styled like leaves.
+    ///
+    /// When enabled, a childless node's label goes through the leaf formatter and gets
+    /// the leaf color instead of the node ones, and no child count is shown for it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::RenderConfig;
+    ///
+    /// let config = RenderConfig::default().with_empty_nodes_as_leaves(true);
+    /// assert!(config.empty_nodes_as_leaves);
+    /// ```
+    pub fn with_empty_nodes_as_leaves(mut self, enabled: bool) -> Self {
+        self.empty_nodes_as_leaves = enabled;
+        self
+    }
+
+    /// Sets a separator for rendering the children of a node on one line.
+    ///
+    /// When every child of a node is a single-line leaf, the children are rendered
+    /// as one leaf joined with `separator`. Nodes with any sub-node or multi-line
+    /// leaf child are rendered as usual, as are nodes where an annotation or glyph
+    /// override targets any of the children, so that it isn't dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::{RenderConfig, Tree};
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["a".to_string()]),
+    ///     Tree::Leaf(vec!["b".to_string()])
+    /// ]);
+    /// let config = RenderConfig::default().with_join_leaf_siblings(", ");
+    /// assert_eq!(tree.render_to_string_with_config(&config), "root\n└─ a, b\n");
+    /// ```
+    pub fn with_join_leaf_siblings(mut self, separator: impl Into<String>) -> Self {
+        self.join_leaf_siblings = Some(separator.into());
+        self
+    }
+
+    /// Classifies a leaf line using the configured classifier, if any.
+    #[cfg(feature = "color")]
+    pub(crate) fn classify(&self, line: &str) -> Severity {
+        self.severity_classifier
+            .as_ref()
+            .map_or(Severity::Normal, |classifier| classifier(line))
+    }
+
+    /// Wraps `content` in an OSC 8 hyperlink if the configured function returns a URL for `line`.
+    pub(crate) fn hyperlink(&self, line: &str, content: String) -> String {
+        match self
+            .hyperlink_fn
+            .as_ref()
+            .and_then(|hyperlink| hyperlink(line))
+        {
+            Some(url) => format!("\x1b]8;;{url}\x1b\\{content}\x1b]8;;\x1b\\"),
+            None => content,
+        }
+    }
+
+    /// Formats the summary line for `hidden` items using the configured function, if any.
+    pub(crate) fn format_summary(&self, hidden: usize) -> String {
+        match &self.summary_fn {
+            Some(summary) => summary(hidden),
+            None => format!("… ({hidden} more)"),
+        }
+    }
+
     /// Formats a node label using the configured formatter, if any.
     pub(crate) fn format_node(&self, label: &str) -> String {
         #[cfg(feature = "formatters")]
@@ -207,6 +895,7 @@ mod tests {
         let config = RenderConfig::default();
         assert!(!config.colors);
         assert_eq!(config.line_ending, "\n");
+        assert!(!config.trim_trailing);
     }
 
     #[test]
@@ -221,6 +910,42 @@ mod tests {
         assert!(config.colors);
     }
 
+    #[test]
+    fn test_with_summary_fn() {
+        let config = RenderConfig::default();
+        assert_eq!(config.format_summary(3), "… (3 more)");
+
+        let config = config.with_summary_fn(|n| format!("{n} weitere"));
+        assert_eq!(config.format_summary(3), "3 weitere");
+        assert_eq!(config.clone().format_summary(1), "1 weitere");
+    }
+
+    #[test]
+    fn test_auto_color_for() {
+        let config = RenderConfig::default()
+            .with_colors(true)
+            .auto_color_for(false);
+        assert!(!config.colors);
+
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        let config = RenderConfig::default().auto_color_for(true);
+        assert_eq!(config.colors, !no_color);
+    }
+
+    #[cfg(feature = "serde-json")]
+    #[test]
+    fn test_serde_partial_config() {
+        let config: RenderConfig =
+            serde_json::from_str(r#"{"style": {"branch": "+- ", "last": "`- "}}"#).unwrap();
+        assert_eq!(config.style.branch, "+- ");
+        assert_eq!(config.style.vertical, StyleConfig::default().vertical);
+        assert_eq!(config.line_ending, "\n");
+
+        let json = serde_json::to_string(&config).unwrap();
+        let roundtrip: RenderConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(roundtrip.style, config.style);
+    }
+
     #[cfg(feature = "formatters")]
     #[test]
     fn test_with_node_formatter() {
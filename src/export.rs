@@ -1,10 +1,149 @@
-//! Tree export to various formats (HTML, SVG, DOT).
+//! Tree export to various formats (HTML, SVG, DOT, ASCII, outline, and PNG with `export-png`).
 
 use crate::tree::Tree;
 
+/// Options controlling HTML export.
+///
+/// # Examples
+///
+/// ```
+/// use treelog::export::HtmlOptions;
+///
+/// let options = HtmlOptions::default().with_open_to_depth(2);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HtmlOptions {
+    /// Nodes shallower than this depth are rendered expanded (`<details open>`).
+    ///
+    /// The root is at depth 0, so `0` collapses everything and `1` expands only the root.
+    pub open_to_depth: usize,
+}
+
+impl HtmlOptions {
+    /// Creates HTML options with all nodes collapsed.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the depth up to which nodes are rendered expanded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::export::HtmlOptions;
+    ///
+    /// let options = HtmlOptions::new().with_open_to_depth(1);
+    /// assert_eq!(options.open_to_depth, 1);
+    /// ```
+    pub fn with_open_to_depth(mut self, depth: usize) -> Self {
+        self.open_to_depth = depth;
+        self
+    }
+}
+
+/// Layout of the SVG diagram produced by [`Tree::to_svg_with_options`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SvgLayoutMode {
+    /// Children stacked below their parent and indented to the right, as in [`Tree::to_svg`]
+    #[default]
+    Indented,
+    /// Root at the center with each level on a ring further out, and every node's
+    /// children spread over the angle given to that node
+    Radial,
+}
+
+/// Options controlling SVG export.
+///
+/// # Examples
+///
+/// ```
+/// use treelog::export::{SvgLayoutMode, SvgOptions};
+///
+/// let options = SvgOptions::default().with_layout(SvgLayoutMode::Radial);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SvgOptions {
+    /// How elements are positioned in the diagram.
+    pub layout: SvgLayoutMode,
+}
+
+impl SvgOptions {
+    /// Creates SVG options using the indented layout.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the layout mode.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::export::{SvgLayoutMode, SvgOptions};
+    ///
+    /// let options = SvgOptions::new().with_layout(SvgLayoutMode::Radial);
+    /// assert_eq!(options.layout, SvgLayoutMode::Radial);
+    /// ```
+    pub fn with_layout(mut self, layout: SvgLayoutMode) -> Self {
+        self.layout = layout;
+        self
+    }
+}
+
+/// Options controlling PNG export.
+///
+/// # Examples
+///
+/// ```
+/// use treelog::export::PngOptions;
+///
+/// let options = PngOptions::default().with_scale(2.0);
+/// ```
+#[cfg(feature = "export-png")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PngOptions {
+    /// Scale factor applied to the SVG dimensions when rasterizing.
+    pub scale: f32,
+}
+
+#[cfg(feature = "export-png")]
+impl Default for PngOptions {
+    fn default() -> Self {
+        PngOptions { scale: 1.0 }
+    }
+}
+
+#[cfg(feature = "export-png")]
+impl PngOptions {
+    /// Creates PNG options rendering at the SVG's natural size.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the scale factor used when rasterizing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::export::PngOptions;
+    ///
+    /// let options = PngOptions::new().with_scale(2.0);
+    /// assert_eq!(options.scale, 2.0);
+    /// ```
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+}
+
 impl Tree {
     /// Exports the tree as HTML with collapsible nodes.
     ///
+    /// All nodes start collapsed; use [`Tree::to_html_with_options`] to expand
+    /// the top levels.
+    ///
     /// Requires the `export` feature.
     ///
     /// # Examples
@@ -18,6 +157,25 @@ impl Tree {
     /// let html = tree.to_html();
     /// ```
     pub fn to_html(&self) -> String {
+        self.to_html_with_options(&HtmlOptions::default())
+    }
+
+    /// Exports the tree as HTML with collapsible nodes using custom options.
+    ///
+    /// Requires the `export` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::{Tree, export::HtmlOptions};
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Node("child".to_string(), vec![Tree::Leaf(vec!["item".to_string()])])
+    /// ]);
+    /// let html = tree.to_html_with_options(&HtmlOptions::new().with_open_to_depth(1));
+    /// assert!(html.contains("<details open>"));
+    /// ```
+    pub fn to_html_with_options(&self, options: &HtmlOptions) -> String {
         let mut html = String::from(
             r#"<div class="tree">
 <style>
@@ -33,22 +191,58 @@ body { font-family: Helvetica Neue, Helvetica, Arial, sans-serif; font-size: 20p
 <ul>
 "#,
         );
-        self.to_html_recursive(&mut html, 0);
+        self.to_html_recursive(&mut html, 0, 0, options);
         html.push_str("</ul></div>");
         html
     }
 
-    fn to_html_recursive(&self, html: &mut String, depth: usize) {
+    /// Exports the tree as a bare HTML `<ul>` list, without the `<style>` block or outer `<div>`.
+    ///
+    /// Use this to embed the tree in an existing page and style it with your own
+    /// CSS. The markup is the same as [`Tree::to_html`] produces inside its wrapper.
+    ///
+    /// Requires the `export` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["item".to_string()])
+    /// ]);
+    /// let html = tree.to_html_fragment();
+    /// assert!(html.starts_with("<ul>"));
+    /// ```
+    pub fn to_html_fragment(&self) -> String {
+        let mut html = String::from("<ul>\n");
+        self.to_html_recursive(&mut html, 0, 0, &HtmlOptions::default());
+        html.push_str("</ul>");
+        html
+    }
+
+    fn to_html_recursive(
+        &self,
+        html: &mut String,
+        depth: usize,
+        level: usize,
+        options: &HtmlOptions,
+    ) {
         match self {
             Tree::Node(label, children) => {
                 let indent = "  ".repeat(depth);
                 if !children.is_empty() {
+                    let details = if level < options.open_to_depth {
+                        "<details open>"
+                    } else {
+                        "<details>"
+                    };
                     html.push_str(&format!(
-                        "{indent}<li>\n{indent}  <details>\n{indent}    <summary>{}</summary>\n{indent}    <ul>\n",
+                        "{indent}<li>\n{indent}  {details}\n{indent}    <summary>{}</summary>\n{indent}    <ul>\n",
                         html_escape(label)
                     ));
                     for child in children {
-                        child.to_html_recursive(html, depth + 2);
+                        child.to_html_recursive(html, depth + 2, level + 1, options);
                     }
                     html.push_str(&format!(
                         "{indent}    </ul>\n{indent}  </details>\n{indent}</li>\n"
@@ -81,6 +275,66 @@ body { font-family: Helvetica Neue, Helvetica, Arial, sans-serif; font-size: 20p
     /// let svg = tree.to_svg();
     /// ```
     pub fn to_svg(&self) -> String {
+        self.svg_document(false)
+    }
+
+    /// Exports the tree as an SVG tree diagram whose subtrees can be collapsed.
+    ///
+    /// Each node is wrapped in a `<g class="tree-node">` group. Clicking the label
+    /// of a node with children toggles the `collapsed` class on its group, which
+    /// hides the group's `tree-children`, much like an HTML `<details>` element.
+    /// The layout is not recomputed, so a collapsed subtree leaves a gap. The
+    /// toggling relies on an embedded script, so it only works where the SVG is
+    /// displayed with scripting enabled, such as when opened directly in a browser.
+    ///
+    /// Requires the `export` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["item".to_string()])
+    /// ]);
+    /// let svg = tree.to_svg_interactive();
+    /// assert!(svg.contains("onclick"));
+    /// ```
+    pub fn to_svg_interactive(&self) -> String {
+        self.svg_document(true)
+    }
+
+    /// Exports the tree as an SVG tree diagram using the given options.
+    ///
+    /// With [`SvgLayoutMode::Indented`] the output equals [`to_svg`](Self::to_svg).
+    /// With [`SvgLayoutMode::Radial`] the root is drawn at the center, each level
+    /// on a ring further out, and every node's angle is split among its children in
+    /// proportion to the number of leaves below them, which suits wide fan-outs.
+    ///
+    /// Requires the `export` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    /// use treelog::export::{SvgLayoutMode, SvgOptions};
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["a".to_string()]),
+    ///     Tree::Leaf(vec!["b".to_string()])
+    /// ]);
+    /// let options = SvgOptions::new().with_layout(SvgLayoutMode::Radial);
+    /// let svg = tree.to_svg_with_options(&options);
+    /// assert!(svg.starts_with("<svg"));
+    /// ```
+    pub fn to_svg_with_options(&self, options: &SvgOptions) -> String {
+        match options.layout {
+            SvgLayoutMode::Indented => self.svg_document(false),
+            SvgLayoutMode::Radial => self.radial_svg_document(),
+        }
+    }
+
+    fn svg_document(&self, interactive: bool) -> String {
         // Calculate layout dimensions
         let mut layout = SvgLayout::new();
         layout.calculate_layout(self);
@@ -89,42 +343,137 @@ body { font-family: Helvetica Neue, Helvetica, Arial, sans-serif; font-size: 20p
         let width = layout.max_x + padding * 2.0;
         let height = layout.max_y + padding * 2.0;
 
-        let mut svg = format!(
-            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.0}\" height=\"{:.0}\">\n<style>\n  .node-text {{ fill: #4C74B9; font-family: Helvetica Neue, Helvetica, Arial, sans-serif; font-size: 20px; }}\n  .leaf-text {{ fill: #212b2b; font-family: Helvetica Neue, Helvetica, Arial, sans-serif; font-size: 20px; }}\n  .tree-line {{ stroke: #c0d1d1; stroke-width: 3; }}\n</style>\n",
-            width, height
-        );
+        let mut svg = svg_header(width, height);
+        if interactive {
+            svg.push_str("  .toggle { cursor: pointer; }\n  .collapsed > .tree-children { display: none; }\n</style>\n<script>\n  function toggle(label) { label.parentNode.classList.toggle('collapsed'); }\n</script>\n");
+        } else {
+            svg.push_str("</style>\n");
+        }
 
-        self.to_svg_recursive(&mut svg, padding, padding);
+        self.to_svg_recursive(&mut svg, padding, padding, interactive);
         svg.push_str("</svg>");
         svg
     }
 
-    fn to_svg_recursive(&self, svg: &mut String, x: f64, y: f64) {
+    fn radial_svg_document(&self) -> String {
+        let mut layout = RadialLayout::default();
+        layout.place(self, 0.0, std::f64::consts::TAU, 0, None);
+
+        // Shift everything so the leftmost and topmost text starts at the padding
+        let padding = 20.0;
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = (0.0f64, 0.0f64, 0.0f64, 0.0f64);
+        for (x, y, _, text) in &layout.texts {
+            let half_width = estimate_text_width(text) / 2.0;
+            min_x = min_x.min(x - half_width);
+            max_x = max_x.max(x + half_width);
+            min_y = min_y.min(y - 15.0);
+            max_y = max_y.max(y + 15.0);
+        }
+        let (dx, dy) = (padding - min_x, padding - min_y);
+
+        let mut svg = svg_header(max_x - min_x + padding * 2.0, max_y - min_y + padding * 2.0);
+        svg.push_str("</style>\n");
+        for (x1, y1, x2, y2) in &layout.lines {
+            svg.push_str(&format!(
+                r#"  <line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" class="tree-line"/>
+"#,
+                x1 + dx,
+                y1 + dy,
+                x2 + dx,
+                y2 + dy
+            ));
+        }
+        for (x, y, class, text) in &layout.texts {
+            svg.push_str(&format!(
+                r#"  <text x="{:.1}" y="{:.1}" class="{}" text-anchor="middle" dominant-baseline="middle">{}</text>
+"#,
+                x + dx,
+                y + dy,
+                class,
+                svg_escape(text)
+            ));
+        }
+        svg.push_str("</svg>");
+        svg
+    }
+
+    /// Exports the tree as a PNG image by rasterizing the [`to_svg`](Self::to_svg) output.
+    ///
+    /// Text is rendered with fonts found on the system; labels are omitted if no
+    /// suitable font is available. Returns an error if the SVG cannot be parsed,
+    /// the scaled size is empty, or encoding fails.
+    ///
+    /// Requires the `export-png` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    /// use treelog::export::PngOptions;
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["item".to_string()])
+    /// ]);
+    /// let png = tree.to_png(&PngOptions::default()).unwrap();
+    /// assert!(png.starts_with(b"\x89PNG"));
+    /// ```
+    #[cfg(feature = "export-png")]
+    pub fn to_png(&self, options: &PngOptions) -> Result<Vec<u8>, String> {
+        use resvg::{tiny_skia, usvg};
+
+        let mut svg_options = usvg::Options::default();
+        svg_options.fontdb_mut().load_system_fonts();
+        let svg = usvg::Tree::from_str(&self.to_svg(), &svg_options).map_err(|e| e.to_string())?;
+
+        let size = svg
+            .size()
+            .to_int_size()
+            .scale_by(options.scale)
+            .ok_or_else(|| format!("invalid PNG scale {}", options.scale))?;
+        let mut pixmap = tiny_skia::Pixmap::new(size.width(), size.height())
+            .ok_or_else(|| "PNG size must be non-zero".to_string())?;
+        let transform = tiny_skia::Transform::from_scale(options.scale, options.scale);
+        resvg::render(&svg, transform, &mut pixmap.as_mut());
+        pixmap.encode_png().map_err(|e| e.to_string())
+    }
+
+    /// Draws the tree at the given position, returning the height it takes up.
+    fn to_svg_recursive(&self, svg: &mut String, x: f64, y: f64, interactive: bool) -> f64 {
         match self {
             Tree::Node(label, children) => {
                 let node_x = x;
                 let node_y = y;
+                let toggle = interactive && !children.is_empty();
+
+                if interactive {
+                    svg.push_str("  <g class=\"tree-node\">\n");
+                }
 
                 // Draw text with middle baseline (centered vertically)
                 svg.push_str(&format!(
-                    r#"  <text x="{}" y="{}" class="node-text" dominant-baseline="middle">{}</text>
+                    r#"  <text x="{}" y="{}" class="node-text{}" dominant-baseline="middle"{}>{}</text>
 "#,
                     node_x,
                     node_y,
+                    if toggle { " toggle" } else { "" },
+                    if toggle {
+                        r#" onclick="toggle(this)""#
+                    } else {
+                        ""
+                    },
                     svg_escape(label)
                 ));
 
+                if toggle {
+                    svg.push_str("  <g class=\"tree-children\">\n");
+                }
+
+                let mut total_height = 0.0;
                 if !children.is_empty() {
                     let child_start_y = node_y + 30.0;
                     let mut child_y = child_start_y;
                     let vertical_line_x = x + 2.5;
 
-                    // Calculate total height of all children (recursively)
-                    let mut total_height = 0.0;
-                    for child in children.iter() {
-                        total_height += calculate_tree_height(child);
-                    }
-
                     // Track the last child's y position for the vertical line
                     let mut last_child_y = child_start_y;
 
@@ -139,13 +488,15 @@ body { font-family: Helvetica Neue, Helvetica, Arial, sans-serif; font-size: 20p
                             vertical_line_x, child_y, child_x, child_y
                         ));
 
-                        child.to_svg_recursive(svg, child_x, child_y);
+                        let child_height =
+                            child.to_svg_recursive(svg, child_x, child_y, interactive);
 
                         // Track this child's position for the vertical line
                         last_child_y = child_y;
 
-                        // Calculate next child position using recursive height
-                        child_y += calculate_tree_height(child);
+                        // Move past the child's whole subtree
+                        child_y += child_height;
+                        total_height += child_height;
                     }
 
                     // Draw vertical line from parent through all children
@@ -162,6 +513,14 @@ body { font-family: Helvetica Neue, Helvetica, Arial, sans-serif; font-size: 20p
                         ));
                     }
                 }
+
+                if toggle {
+                    svg.push_str("  </g>\n");
+                }
+                if interactive {
+                    svg.push_str("  </g>\n");
+                }
+                30.0 + total_height
             }
             Tree::Leaf(lines) => {
                 let mut leaf_y = y;
@@ -175,10 +534,210 @@ body { font-family: Helvetica Neue, Helvetica, Arial, sans-serif; font-size: 20p
                     ));
                     leaf_y += 30.0;
                 }
+                lines.len() as f64 * 30.0
+            }
+        }
+    }
+
+    /// Exports the tree as a left-to-right ASCII diagram.
+    ///
+    /// The root is drawn on the left and each node's children fan out to its
+    /// right, one per row. Lines of multi-line leaves are stacked vertically.
+    ///
+    /// Requires the `export` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Node("a".to_string(), vec![
+    ///         Tree::Leaf(vec!["a1".to_string()]),
+    ///         Tree::Leaf(vec!["a2".to_string()])
+    ///     ]),
+    ///     Tree::Leaf(vec!["b".to_string()])
+    /// ]);
+    /// assert_eq!(
+    ///     tree.to_horizontal_ascii(),
+    ///     "root -+- a -+- a1\n      |     `- a2\n      `- b\n"
+    /// );
+    /// ```
+    pub fn to_horizontal_ascii(&self) -> String {
+        let mut output = String::new();
+        for line in self.horizontal_ascii_lines() {
+            output.push_str(&line);
+            output.push('\n');
+        }
+        output
+    }
+
+    fn horizontal_ascii_lines(&self) -> Vec<String> {
+        match self {
+            Tree::Leaf(lines) => lines.clone(),
+            Tree::Node(label, children) if children.is_empty() => vec![label.clone()],
+            Tree::Node(label, children) => {
+                let head = format!("{label} -");
+                let indent = " ".repeat(head.chars().count());
+                let mut result = Vec::new();
+                for (index, child) in children.iter().enumerate() {
+                    let is_first = index == 0;
+                    let is_last = index == children.len() - 1;
+                    let (connector, continuation) = match (is_first, is_last) {
+                        (true, true) => ("--", "  "),
+                        (_, false) => ("+-", "| "),
+                        (false, true) => ("`-", "  "),
+                    };
+                    for (row, line) in child.horizontal_ascii_lines().into_iter().enumerate() {
+                        let start = if is_first && row == 0 { &head } else { &indent };
+                        let branch = if row == 0 { connector } else { continuation };
+                        let line = format!("{start}{branch} {line}");
+                        result.push(line.trim_end().to_string());
+                    }
+                }
+                result
             }
         }
     }
 
+    /// Exports the tree as numbered outline text.
+    ///
+    /// Every element gets a dotted number built from its position: the root is
+    /// `1.`, its children `1.1`, `1.2`, and so on. Entries are indented two spaces
+    /// per level, and extra lines of multi-line leaves are indented to align with
+    /// the first. Empty leaves are omitted.
+    ///
+    /// Requires the `export` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Node("child".to_string(), vec![
+    ///         Tree::Leaf(vec!["leaf".to_string()])
+    ///     ]),
+    ///     Tree::Leaf(vec!["other".to_string()])
+    /// ]);
+    /// assert_eq!(
+    ///     tree.to_outline(),
+    ///     "1. root\n  1.1 child\n    1.1.1 leaf\n  1.2 other\n"
+    /// );
+    /// ```
+    pub fn to_outline(&self) -> String {
+        let mut output = String::new();
+        self.write_outline(&mut output, "1.");
+        output
+    }
+
+    fn write_outline(&self, output: &mut String, number: &str) {
+        let depth = number.matches('.').count() - usize::from(number.ends_with('.'));
+        let indent = "  ".repeat(depth);
+        match self {
+            Tree::Node(label, children) => {
+                output.push_str(&format!("{indent}{number} {label}\n"));
+                let base = number.trim_end_matches('.');
+                let mut index = 0;
+                for child in children {
+                    if matches!(child, Tree::Leaf(lines) if lines.is_empty()) {
+                        continue;
+                    }
+                    index += 1;
+                    child.write_outline(output, &format!("{base}.{index}"));
+                }
+            }
+            Tree::Leaf(lines) => {
+                let continuation = " ".repeat(indent.len() + number.len() + 1);
+                for (i, line) in lines.iter().enumerate() {
+                    if i == 0 {
+                        output.push_str(&format!("{indent}{number} {line}\n"));
+                    } else {
+                        output.push_str(&format!("{continuation}{line}\n"));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Parses numbered outline text, as produced by [`to_outline`](Tree::to_outline).
+    ///
+    /// The nesting depth of each entry comes from the number of segments in its
+    /// dotted number; indentation before the number is ignored. Entries with
+    /// children become nodes and entries without children become leaves. Lines
+    /// indented to the column where the previous entry's text starts continue that
+    /// entry as extra leaf lines, keeping any further whitespace, so outlines
+    /// written by `to_outline` parse back exactly. Other lines that don't start
+    /// with a number also continue the previous entry, with surrounding whitespace
+    /// trimmed.
+    ///
+    /// Requires the `export` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the outline is empty, has more than one root, skips a
+    /// level, or starts with an unnumbered line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let tree = Tree::from_outline("1. root\n  1.1 item\n").unwrap();
+    /// assert_eq!(tree, Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["item".to_string()])
+    /// ]));
+    /// ```
+    pub fn from_outline(text: &str) -> Result<Tree, String> {
+        // Open entries from the root down: (lines, children)
+        let mut stack: Vec<(Vec<String>, Vec<Tree>)> = Vec::new();
+        let mut root = None;
+        // Column at which the text of the most recent entry starts
+        let mut text_column = 0;
+        for (line_number, line) in text.lines().enumerate() {
+            if let Some((lines, _)) = stack.last_mut()
+                && line.len() >= text_column
+                && line.bytes().take(text_column).all(|b| b == b' ')
+            {
+                lines.push(line[text_column..].to_string());
+                continue;
+            }
+            if line.trim().is_empty() {
+                continue;
+            }
+            let Some((depth, column, content)) = parse_outline_entry(line) else {
+                match stack.last_mut() {
+                    Some((lines, _)) => {
+                        lines.push(line.trim().to_string());
+                        continue;
+                    }
+                    None => return Err(format!("line {}: expected a number", line_number + 1)),
+                }
+            };
+            if depth > stack.len() + 1 {
+                return Err(format!(
+                    "line {}: skipped an outline level",
+                    line_number + 1
+                ));
+            }
+            while stack.len() >= depth {
+                close_outline_entry(&mut stack, &mut root);
+            }
+            if depth == 1 && root.is_some() {
+                return Err(format!(
+                    "line {}: outline has more than one root",
+                    line_number + 1
+                ));
+            }
+            stack.push((vec![content.to_string()], Vec::new()));
+            text_column = column;
+        }
+        while !stack.is_empty() {
+            close_outline_entry(&mut stack, &mut root);
+        }
+        root.ok_or_else(|| "outline is empty".to_string())
+    }
+
     /// Exports the tree as Graphviz DOT format.
     ///
     /// Requires the `export` feature.
@@ -235,6 +794,77 @@ body { font-family: Helvetica Neue, Helvetica, Arial, sans-serif; font-size: 20p
     }
 }
 
+/// Element positions for the radial SVG layout, centered on the root.
+#[derive(Default)]
+struct RadialLayout {
+    /// Text position, CSS class and content
+    texts: Vec<(f64, f64, &'static str, String)>,
+    /// Connector end points
+    lines: Vec<(f64, f64, f64, f64)>,
+}
+
+impl RadialLayout {
+    /// Distance between consecutive rings.
+    const RING: f64 = 120.0;
+
+    /// Places `tree` in the middle of the angle from `start` to `end`, `depth` rings out.
+    fn place(
+        &mut self,
+        tree: &Tree,
+        start: f64,
+        end: f64,
+        depth: usize,
+        parent: Option<(f64, f64)>,
+    ) {
+        let angle = (start + end) / 2.0;
+        let radius = depth as f64 * Self::RING;
+        let (x, y) = (radius * angle.cos(), radius * angle.sin());
+        if let Some((parent_x, parent_y)) = parent {
+            self.lines.push((parent_x, parent_y, x, y));
+        }
+        match tree {
+            Tree::Node(label, children) => {
+                self.texts.push((x, y, "node-text", label.clone()));
+                let total: usize = children.iter().map(radial_weight).sum();
+                let mut child_start = start;
+                for child in children {
+                    let span = (end - start) * radial_weight(child) as f64 / total as f64;
+                    self.place(
+                        child,
+                        child_start,
+                        child_start + span,
+                        depth + 1,
+                        Some((x, y)),
+                    );
+                    child_start += span;
+                }
+            }
+            Tree::Leaf(lines) => {
+                for (index, line) in lines.iter().enumerate() {
+                    self.texts
+                        .push((x, y + index as f64 * 30.0, "leaf-text", line.clone()));
+                }
+            }
+        }
+    }
+}
+
+/// Share of its parent's angle given to an element in the radial layout: its leaf count.
+fn radial_weight(tree: &Tree) -> usize {
+    match tree {
+        Tree::Node(_, children) => children.iter().map(radial_weight).sum::<usize>().max(1),
+        Tree::Leaf(_) => 1,
+    }
+}
+
+/// Opens an SVG document and its style block with the shared text and line classes.
+fn svg_header(width: f64, height: f64) -> String {
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.0}\" height=\"{:.0}\">\n<style>\n  .node-text {{ fill: #4C74B9; font-family: Helvetica Neue, Helvetica, Arial, sans-serif; font-size: 20px; }}\n  .leaf-text {{ fill: #212b2b; font-family: Helvetica Neue, Helvetica, Arial, sans-serif; font-size: 20px; }}\n  .tree-line {{ stroke: #c0d1d1; stroke-width: 3; }}\n",
+        width, height
+    )
+}
+
 struct SvgLayout {
     max_x: f64,
     max_y: f64,
@@ -252,27 +882,27 @@ impl SvgLayout {
         self.calculate_recursive(tree, 0.0, 0.0);
     }
 
-    fn calculate_recursive(&mut self, tree: &Tree, x: f64, y: f64) {
+    /// Measures the tree at the given position, returning the height it takes up.
+    fn calculate_recursive(&mut self, tree: &Tree, x: f64, y: f64) -> f64 {
         match tree {
             Tree::Node(label, children) => {
                 // Track max_x for this node's label
                 self.max_x = self.max_x.max(x + estimate_text_width(label));
                 self.max_y = self.max_y.max(y);
 
+                let child_start_y = y + 30.0;
+                let mut child_y = child_start_y;
                 if !children.is_empty() {
-                    let child_start_y = y + 30.0;
-                    let mut child_y = child_start_y;
-
                     for child in children {
                         let child_x = x + 30.0;
-                        // Recursively calculate layout for child
-                        self.calculate_recursive(child, child_x, child_y);
-                        // Move to next child position (exactly like rendering does)
-                        child_y += calculate_tree_height(child);
+                        // Recursively calculate layout for child and move past it
+                        // (exactly like rendering does)
+                        child_y += self.calculate_recursive(child, child_x, child_y);
                     }
                     // Track the final y position after all children
                     self.max_y = self.max_y.max(child_y);
                 }
+                child_y - y
             }
             Tree::Leaf(lines) => {
                 // Track max_x for leaf text
@@ -285,6 +915,7 @@ impl SvgLayout {
                     self.max_y = self.max_y.max(leaf_y);
                     leaf_y += 30.0;
                 }
+                leaf_y - y
             }
         }
     }
@@ -295,24 +926,6 @@ fn estimate_text_width(text: &str) -> f64 {
     text.len() as f64 * 20.0 * 0.6
 }
 
-fn calculate_tree_height(tree: &Tree) -> f64 {
-    match tree {
-        Tree::Node(_, children) => {
-            if children.is_empty() {
-                30.0
-            } else {
-                // Node itself takes 30px, plus all children
-                let mut total = 30.0;
-                for child in children {
-                    total += calculate_tree_height(child);
-                }
-                total
-            }
-        }
-        Tree::Leaf(lines) => (lines.len() as f64) * 30.0,
-    }
-}
-
 fn html_escape(s: &str) -> String {
     s.replace('&', "&amp;")
         .replace('<', "&lt;")
@@ -334,6 +947,38 @@ fn dot_escape(s: &str) -> String {
         .replace('\n', "\\n")
 }
 
+/// Splits an outline line into its depth, the column its text starts at, and the
+/// text, if it starts with a dotted number.
+fn parse_outline_entry(line: &str) -> Option<(usize, usize, &str)> {
+    let trimmed = line.trim_start();
+    let (number, content) = trimmed.split_once(' ').unwrap_or((trimmed, ""));
+    let segments: Vec<&str> = number.trim_end_matches('.').split('.').collect();
+    if segments
+        .iter()
+        .any(|segment| segment.is_empty() || !segment.bytes().all(|b| b.is_ascii_digit()))
+    {
+        return None;
+    }
+    let column = line.len() - trimmed.len() + number.len() + 1;
+    Some((segments.len(), column, content))
+}
+
+/// Pops the innermost open outline entry and attaches it to its parent.
+fn close_outline_entry(stack: &mut Vec<(Vec<String>, Vec<Tree>)>, root: &mut Option<Tree>) {
+    let Some((mut lines, children)) = stack.pop() else {
+        return;
+    };
+    let tree = if children.is_empty() {
+        Tree::Leaf(lines)
+    } else {
+        Tree::Node(lines.remove(0), children)
+    };
+    match stack.last_mut() {
+        Some((_, siblings)) => siblings.push(tree),
+        None => *root = Some(tree),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -349,6 +994,43 @@ mod tests {
         assert!(html.contains("item"));
     }
 
+    #[test]
+    fn test_to_html_open_to_depth() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![Tree::Node(
+                "child".to_string(),
+                vec![Tree::Node(
+                    "grandchild".to_string(),
+                    vec![Tree::Leaf(vec!["item".to_string()])],
+                )],
+            )],
+        );
+        let html = tree.to_html_with_options(&HtmlOptions::new().with_open_to_depth(2));
+        assert_eq!(html.matches("<details open>").count(), 2);
+        assert_eq!(html.matches("<details>").count(), 1);
+        let grandchild = html.find("<summary>grandchild</summary>").unwrap();
+        let details = html[..grandchild].rfind("<details").unwrap();
+        assert!(html[details..grandchild].starts_with("<details>"));
+
+        let collapsed = tree.to_html();
+        assert!(!collapsed.contains("<details open>"));
+    }
+
+    #[test]
+    fn test_to_html_fragment() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![Tree::Leaf(vec!["item".to_string()])],
+        );
+        let fragment = tree.to_html_fragment();
+        assert!(fragment.contains("<ul>"));
+        assert!(fragment.contains("<summary>root</summary>"));
+        assert!(!fragment.contains("<style>"));
+        assert!(!fragment.contains("<div"));
+        assert!(tree.to_html().contains(&fragment));
+    }
+
     #[test]
     fn test_to_svg() {
         let tree = Tree::Node(
@@ -360,6 +1042,239 @@ mod tests {
         assert!(svg.contains("<svg"));
     }
 
+    #[test]
+    fn test_to_svg_child_positions() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node(
+                    "a".to_string(),
+                    vec![Tree::Leaf(vec!["a1".to_string(), "a2".to_string()])],
+                ),
+                Tree::Leaf(vec!["b".to_string()]),
+            ],
+        );
+        let svg = tree.to_svg();
+        let text = |content: &str| {
+            svg.lines()
+                .find(|line| line.ends_with(&format!(">{content}</text>")))
+                .unwrap()
+        };
+        assert!(text("root").contains(r#"x="20" y="20""#));
+        assert!(text("a").contains(r#"x="50" y="50""#));
+        assert!(text("a1").contains(r#"x="80" y="80""#));
+        assert!(text("a2").contains(r#"x="80" y="110""#));
+        assert!(text("b").contains(r#"x="50" y="140""#));
+        assert!(svg.contains(r#"<line x1="22.5" y1="30" x2="22.5" y2="140" class="tree-line"/>"#));
+        assert!(svg.contains(r#"height="190""#));
+    }
+
+    #[test]
+    fn test_to_svg_radial() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node(
+                    "a".to_string(),
+                    vec![
+                        Tree::Leaf(vec!["a1".to_string()]),
+                        Tree::Leaf(vec!["a2".to_string()]),
+                    ],
+                ),
+                Tree::Leaf(vec!["b".to_string()]),
+                Tree::Leaf(vec!["c".to_string()]),
+            ],
+        );
+        let position = |svg: &str, text: &str| -> (f64, f64) {
+            let line = svg
+                .lines()
+                .find(|line| line.contains(&format!(">{text}</text>")))
+                .unwrap();
+            let attribute = |name: &str| -> f64 {
+                let start = line.find(&format!(" {name}=\"")).unwrap() + name.len() + 3;
+                let end = start + line[start..].find('"').unwrap();
+                line[start..end].parse().unwrap()
+            };
+            (attribute("x"), attribute("y"))
+        };
+
+        let indented = tree.to_svg_with_options(&SvgOptions::default());
+        assert_eq!(indented, tree.to_svg());
+        let radial =
+            tree.to_svg_with_options(&SvgOptions::new().with_layout(SvgLayoutMode::Radial));
+        assert_eq!(
+            radial.matches("<text").count(),
+            indented.matches("<text").count()
+        );
+        assert_eq!(radial.matches("<line").count(), 5);
+
+        let distance = |(x1, y1): (f64, f64), (x2, y2): (f64, f64)| (x1 - x2).hypot(y1 - y2);
+        let root = position(&radial, "root");
+        for label in ["a", "b", "c"] {
+            assert_ne!(position(&radial, label), position(&indented, label));
+            assert!((distance(root, position(&radial, label)) - 120.0).abs() < 0.2);
+        }
+        for label in ["a1", "a2"] {
+            assert!((distance(root, position(&radial, label)) - 240.0).abs() < 0.2);
+        }
+    }
+
+    #[test]
+    fn test_to_svg_interactive() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node(
+                    "dir".to_string(),
+                    vec![Tree::Leaf(vec!["a <b>".to_string()])],
+                ),
+                Tree::Node("empty".to_string(), vec![]),
+            ],
+        );
+        let svg = tree.to_svg_interactive();
+        assert_eq!(svg.matches(r#"<g class="tree-node">"#).count(), 3);
+        assert_eq!(svg.matches(r#"<g class="tree-children">"#).count(), 2);
+        assert_eq!(svg.matches(r#"onclick="toggle(this)""#).count(), 2);
+        assert_eq!(svg.matches("<g").count(), svg.matches("</g>").count());
+        assert!(svg.contains("function toggle(label)"));
+        assert!(svg.contains(".collapsed > .tree-children { display: none; }"));
+        assert!(svg.contains("a &lt;b&gt;"));
+
+        let svg = tree.to_svg();
+        assert!(!svg.contains("onclick"));
+        assert!(!svg.contains("<g"));
+        assert!(!svg.contains("<script>"));
+    }
+
+    #[cfg(feature = "export-png")]
+    #[test]
+    fn test_to_png() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![Tree::Leaf(vec!["item".to_string()])],
+        );
+        let png = tree.to_png(&PngOptions::default()).unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+        // The IHDR chunk follows the signature and stores big-endian width and height
+        assert_eq!(&png[12..16], b"IHDR");
+        let width = u32::from_be_bytes(png[16..20].try_into().unwrap());
+        let height = u32::from_be_bytes(png[20..24].try_into().unwrap());
+        assert!(width > 0 && height > 0);
+
+        let scaled = tree.to_png(&PngOptions::new().with_scale(2.0)).unwrap();
+        let scaled_width = u32::from_be_bytes(scaled[16..20].try_into().unwrap());
+        assert_eq!(scaled_width, width * 2);
+    }
+
+    #[test]
+    fn test_to_horizontal_ascii() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node(
+                    "a".to_string(),
+                    vec![
+                        Tree::Leaf(vec!["a1".to_string(), "more".to_string()]),
+                        Tree::Node("a2".to_string(), vec![Tree::Leaf(vec!["x".to_string()])]),
+                    ],
+                ),
+                Tree::Node("b".to_string(), vec![]),
+                Tree::Leaf(vec!["c".to_string()]),
+            ],
+        );
+        let ascii = tree.to_horizontal_ascii();
+        assert_eq!(
+            ascii,
+            "root -+- a -+- a1\n      |     |  more\n      |     `- a2 --- x\n      +- b\n      `- c\n"
+        );
+
+        let lines: Vec<&str> = ascii.lines().collect();
+        assert!(lines[0].starts_with("root"));
+        let column = |text: &str| lines.iter().find_map(|line| line.find(text)).unwrap();
+        assert!(column("a ") > column("root"));
+        assert_eq!(column("a "), column("b"));
+        assert_eq!(column("b"), column("c"));
+        assert!(column("a1") > column("a "));
+
+        let leaf = Tree::Leaf(vec!["only".to_string()]);
+        assert_eq!(leaf.to_horizontal_ascii(), "only\n");
+    }
+
+    #[test]
+    fn test_to_outline() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node(
+                    "a".to_string(),
+                    vec![
+                        Tree::Leaf(vec!["a1".to_string()]),
+                        Tree::Leaf(vec!["a2".to_string(), "more".to_string()]),
+                    ],
+                ),
+                Tree::Leaf(vec![]),
+                Tree::Node("b".to_string(), vec![Tree::Leaf(vec!["b1".to_string()])]),
+            ],
+        );
+        let outline = tree.to_outline();
+        assert_eq!(
+            outline,
+            "1. root\n  1.1 a\n    1.1.1 a1\n    1.1.2 a2\n          more\n  1.2 b\n    1.2.1 b1\n"
+        );
+
+        let mut expected = tree.clone();
+        if let Tree::Node(_, children) = &mut expected {
+            children.remove(1);
+        }
+        assert_eq!(Tree::from_outline(&outline), Ok(expected));
+    }
+
+    #[test]
+    fn test_outline_roundtrip_continuation_lines() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Leaf(vec!["total".to_string(), "42 items".to_string()]),
+                Tree::Node(
+                    "  padded  ".to_string(),
+                    vec![Tree::Leaf(vec![
+                        " lead".to_string(),
+                        "1.2 x".to_string(),
+                        "".to_string(),
+                        "  trail  ".to_string(),
+                    ])],
+                ),
+                Tree::Leaf(vec!["last ".to_string()]),
+            ],
+        );
+        let outline = tree.to_outline();
+        assert_eq!(Tree::from_outline(&outline), Ok(tree));
+
+        assert_eq!(
+            Tree::from_outline("1. root\n  1.1 item\n      1 more\n   loose  \n"),
+            Ok(Tree::Node(
+                "root".to_string(),
+                vec![Tree::Leaf(vec![
+                    "item".to_string(),
+                    "1 more".to_string(),
+                    "loose".to_string(),
+                ])]
+            ))
+        );
+    }
+
+    #[test]
+    fn test_from_outline_errors() {
+        assert!(Tree::from_outline("").is_err());
+        assert!(Tree::from_outline("text\n1. root").is_err());
+        assert!(Tree::from_outline("1. root\n1.1.1 deep").is_err());
+        assert!(Tree::from_outline("1. one\n2. two").is_err());
+        assert_eq!(
+            Tree::from_outline("1. only"),
+            Ok(Tree::Leaf(vec!["only".to_string()]))
+        );
+    }
+
     #[test]
     fn test_to_dot() {
         let tree = Tree::Node(
@@ -0,0 +1,278 @@
+//! Deterministic content hashing of trees and subtrees.
+//!
+//! Hashes use 64-bit FNV-1a over a length-prefixed encoding of labels and lines,
+//! so they are stable across runs, platforms and compiler versions. They are
+//! meant for change detection and caching, not for security.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::tree::Tree;
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Incremental 64-bit FNV-1a hasher.
+struct Fnv(u64);
+
+impl Fnv {
+    fn new() -> Self {
+        Fnv(FNV_OFFSET)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_str(&mut self, s: &str) {
+        self.write_u64(s.len() as u64);
+        self.write(s.as_bytes());
+    }
+}
+
+impl Tree {
+    /// Returns a deterministic hash of the tree's full content.
+    ///
+    /// Trees with equal labels, lines and structure have equal hashes, regardless
+    /// of where they appear. The hash is stable across runs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let a = Tree::Node("root".to_string(), vec![Tree::Leaf(vec!["x".to_string()])]);
+    /// let b = Tree::Node("root".to_string(), vec![Tree::Leaf(vec!["y".to_string()])]);
+    /// assert_eq!(a.content_hash(), a.clone().content_hash());
+    /// assert_ne!(a.content_hash(), b.content_hash());
+    /// ```
+    pub fn content_hash(&self) -> u64 {
+        self.hash_recursive(&mut Vec::new(), &mut None)
+    }
+
+    /// Returns the content hash of every subtree, keyed by its path from the root.
+    ///
+    /// The hashes are computed in a single post-order pass, with child hashes
+    /// feeding their parent's hash, and equal [`content_hash`](Self::content_hash)
+    /// for each subtree. Comparing the maps of two versions of a tree shows which
+    /// subtrees changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["item".to_string()])
+    /// ]);
+    /// let hashes = tree.subtree_hashes();
+    /// assert_eq!(hashes.len(), 2);
+    /// assert_eq!(hashes[&vec![]], tree.content_hash());
+    /// ```
+    pub fn subtree_hashes(&self) -> HashMap<Vec<usize>, u64> {
+        let mut hashes = HashMap::new();
+        self.hash_recursive(&mut Vec::new(), &mut Some(&mut hashes));
+        hashes
+    }
+
+    /// Replaces repeated subtrees with references to a shared definition.
+    ///
+    /// Walking the tree in pre-order, the first occurrence of each subtree is
+    /// kept and every later subtree with the same [`content_hash`](Self::content_hash)
+    /// is replaced by a leaf `ref:<hash>`, with the hash as 16 hex digits. The
+    /// returned map holds the original subtree for every referenced hash. Only
+    /// nodes with children are factored, since leaves and childless nodes are no
+    /// larger than a reference.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let dir = Tree::Node("src".to_string(), vec![Tree::Leaf(vec!["lib.rs".to_string()])]);
+    /// let tree = Tree::Node("root".to_string(), vec![dir.clone(), dir.clone()]);
+    /// let (factored, definitions) = tree.factor_common();
+    /// let reference = format!("ref:{:016x}", dir.content_hash());
+    /// assert_eq!(factored, Tree::Node("root".to_string(), vec![
+    ///     dir.clone(),
+    ///     Tree::Leaf(vec![reference])
+    /// ]));
+    /// assert_eq!(definitions[&dir.content_hash()], dir);
+    /// ```
+    pub fn factor_common(&self) -> (Tree, HashMap<u64, Tree>) {
+        let hashes = self.subtree_hashes();
+        let mut seen = HashSet::new();
+        let mut definitions = HashMap::new();
+        let factored = self.factor_recursive(&hashes, &mut Vec::new(), &mut seen, &mut definitions);
+        (factored, definitions)
+    }
+
+    fn factor_recursive(
+        &self,
+        hashes: &HashMap<Vec<usize>, u64>,
+        path: &mut Vec<usize>,
+        seen: &mut HashSet<u64>,
+        definitions: &mut HashMap<u64, Tree>,
+    ) -> Tree {
+        let Tree::Node(label, children) = self else {
+            return self.clone();
+        };
+        if children.is_empty() {
+            return self.clone();
+        }
+        let hash = hashes[path.as_slice()];
+        if !seen.insert(hash) {
+            definitions.entry(hash).or_insert_with(|| self.clone());
+            return Tree::Leaf(vec![format!("ref:{hash:016x}")]);
+        }
+        let mut factored = Vec::with_capacity(children.len());
+        for (index, child) in children.iter().enumerate() {
+            path.push(index);
+            factored.push(child.factor_recursive(hashes, path, seen, definitions));
+            path.pop();
+        }
+        Tree::Node(label.clone(), factored)
+    }
+
+    fn hash_recursive(
+        &self,
+        path: &mut Vec<usize>,
+        hashes: &mut Option<&mut HashMap<Vec<usize>, u64>>,
+    ) -> u64 {
+        let mut hasher = Fnv::new();
+        match self {
+            Tree::Node(label, children) => {
+                hasher.write(&[0]);
+                hasher.write_str(label);
+                hasher.write_u64(children.len() as u64);
+                for (index, child) in children.iter().enumerate() {
+                    path.push(index);
+                    let child_hash = child.hash_recursive(path, hashes);
+                    path.pop();
+                    hasher.write_u64(child_hash);
+                }
+            }
+            Tree::Leaf(lines) => {
+                hasher.write(&[1]);
+                hasher.write_u64(lines.len() as u64);
+                for line in lines {
+                    hasher.write_str(line);
+                }
+            }
+        }
+        if let Some(hashes) = hashes {
+            hashes.insert(path.clone(), hasher.0);
+        }
+        hasher.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dir(files: &[&str]) -> Tree {
+        Tree::Node(
+            "src".to_string(),
+            files
+                .iter()
+                .map(|file| Tree::Leaf(vec![file.to_string()]))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_subtree_hashes() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                dir(&["a.rs", "b.rs"]),
+                Tree::Node("nested".to_string(), vec![dir(&["a.rs", "b.rs"])]),
+                dir(&["a.rs"]),
+            ],
+        );
+        let hashes = tree.subtree_hashes();
+        assert_eq!(hashes.len(), 10);
+        assert_eq!(hashes[&vec![0]], hashes[&vec![1, 0]]);
+        assert_ne!(hashes[&vec![0]], hashes[&vec![2]]);
+        assert_eq!(hashes[&vec![]], tree.content_hash());
+        for (path, hash) in &hashes {
+            let mut subtree = &tree;
+            for &index in path {
+                subtree = &subtree.children().unwrap()[index];
+            }
+            assert_eq!(*hash, subtree.content_hash());
+        }
+    }
+
+    #[test]
+    fn test_content_hash_distinguishes_shapes() {
+        let leaf = |lines: &[&str]| Tree::Leaf(lines.iter().map(|l| l.to_string()).collect());
+        assert_ne!(
+            leaf(&["ab"]).content_hash(),
+            leaf(&["a", "b"]).content_hash()
+        );
+        assert_ne!(
+            Tree::Node("a".to_string(), vec![]).content_hash(),
+            leaf(&["a"]).content_hash()
+        );
+        assert_ne!(
+            Tree::Node("root".to_string(), vec![leaf(&["x"]), leaf(&["y"])]).content_hash(),
+            Tree::Node("root".to_string(), vec![leaf(&["y"]), leaf(&["x"])]).content_hash()
+        );
+        // Pinned so the hash stays stable across releases
+        assert_eq!(Tree::Leaf(vec![]).content_hash(), 0x529a_2cdc_8ff5_33ac);
+    }
+
+    #[test]
+    fn test_factor_common() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                dir(&["a.rs", "b.rs"]),
+                Tree::Leaf(vec!["a.rs".to_string()]),
+                Tree::Node(
+                    "nested".to_string(),
+                    vec![dir(&["a.rs", "b.rs"]), dir(&["c.rs"])],
+                ),
+                Tree::Node(
+                    "nested".to_string(),
+                    vec![dir(&["a.rs", "b.rs"]), dir(&["c.rs"])],
+                ),
+            ],
+        );
+        let (factored, definitions) = tree.factor_common();
+
+        let src_hash = dir(&["a.rs", "b.rs"]).content_hash();
+        let nested_hash = tree.children().unwrap()[2].content_hash();
+        let reference = |hash: u64| Tree::Leaf(vec![format!("ref:{hash:016x}")]);
+        assert_eq!(
+            factored,
+            Tree::Node(
+                "root".to_string(),
+                vec![
+                    dir(&["a.rs", "b.rs"]),
+                    Tree::Leaf(vec!["a.rs".to_string()]),
+                    Tree::Node(
+                        "nested".to_string(),
+                        vec![reference(src_hash), dir(&["c.rs"])],
+                    ),
+                    reference(nested_hash),
+                ],
+            )
+        );
+        assert_eq!(definitions.len(), 2);
+        assert_eq!(definitions[&src_hash], dir(&["a.rs", "b.rs"]));
+        assert_eq!(definitions[&nested_hash], tree.children().unwrap()[2]);
+
+        let (unchanged, definitions) = dir(&["a.rs", "a.rs"]).factor_common();
+        assert_eq!(unchanged, dir(&["a.rs", "a.rs"]));
+        assert!(definitions.is_empty());
+    }
+}
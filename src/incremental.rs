@@ -24,6 +24,8 @@ use std::collections::HashMap;
 
 use crate::level::LevelPath;
 use crate::prefix::compute_prefix;
+#[cfg(feature = "stats")]
+use crate::stats::TreeStats;
 use crate::style::StyleConfig;
 use crate::tree::Tree;
 
@@ -62,6 +64,24 @@ pub struct IncrementalTree {
     root_ids: Vec<usize>,
     /// Style configuration for prefix computation
     style: StyleConfig,
+    /// Statistics maintained as items are added
+    #[cfg(feature = "stats")]
+    stats: StatsTracker,
+}
+
+/// Running statistics for the items of an `IncrementalTree`, excluding any synthetic root.
+#[cfg(feature = "stats")]
+#[derive(Default)]
+struct StatsTracker {
+    /// Maps item ID to its depth below its root
+    depths: HashMap<usize, usize>,
+    /// (node count, leaf count) at each depth
+    breakdown: Vec<(usize, usize)>,
+    /// Maximum number of children of any node
+    width: usize,
+    node_count: usize,
+    leaf_count: usize,
+    total_lines: usize,
 }
 
 impl IncrementalTree {
@@ -79,6 +99,8 @@ impl IncrementalTree {
             next_id: 0,
             root_ids: Vec::new(),
             style,
+            #[cfg(feature = "stats")]
+            stats: StatsTracker::default(),
         }
     }
 
@@ -103,6 +125,81 @@ impl IncrementalTree {
             self.root_ids.push(id);
             self.child_to_parent.insert(id, None);
         }
+
+        #[cfg(feature = "stats")]
+        self.record_stats(id);
+    }
+
+    /// Updates the running statistics for a newly linked item.
+    #[cfg(feature = "stats")]
+    fn record_stats(&mut self, id: usize) {
+        let stats = &mut self.stats;
+        let depth = match self.child_to_parent.get(&id).copied().flatten() {
+            Some(parent_id) => {
+                let siblings = self.parent_to_children.get(&parent_id).map_or(0, Vec::len);
+                stats.width = stats.width.max(siblings);
+                stats.depths.get(&parent_id).map_or(0, |depth| depth + 1)
+            }
+            None => 0,
+        };
+        stats.depths.insert(id, depth);
+        if stats.breakdown.len() <= depth {
+            stats.breakdown.resize(depth + 1, (0, 0));
+        }
+        match self.trees.get(&id) {
+            Some(Tree::Node(..)) => {
+                stats.breakdown[depth].0 += 1;
+                stats.node_count += 1;
+            }
+            Some(Tree::Leaf(lines)) => {
+                stats.breakdown[depth].1 += 1;
+                stats.leaf_count += 1;
+                stats.total_lines += lines.len();
+            }
+            None => {}
+        }
+    }
+
+    /// Returns statistics about the tree without rebuilding it.
+    ///
+    /// The statistics are maintained as items are added, so this is cheap to call
+    /// after every insertion. They match `build_tree().unwrap().stats()`, including
+    /// the synthetic root added when there are multiple roots. An empty tree
+    /// reports all zeros.
+    ///
+    /// Requires the `stats` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use treelog::incremental::IncrementalTree;
+    ///
+    /// let mut tree = IncrementalTree::new();
+    /// let root_id = tree.add_node("root", None);
+    /// tree.add_leaf("item", Some(root_id));
+    ///
+    /// let stats = tree.stats();
+    /// assert_eq!(stats.leaf_count, 1);
+    /// ```
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> TreeStats {
+        let stats = &self.stats;
+        let mut result = TreeStats {
+            depth: stats.breakdown.len().saturating_sub(1),
+            width: stats.width,
+            node_count: stats.node_count,
+            leaf_count: stats.leaf_count,
+            total_lines: stats.total_lines,
+            breakdown: stats.breakdown.clone(),
+        };
+        if self.root_ids.len() > 1 {
+            // Account for the synthetic root wrapping multiple roots in `build_tree`
+            result.depth += 1;
+            result.width = result.width.max(self.root_ids.len());
+            result.node_count += 1;
+            result.breakdown.insert(0, (1, 0));
+        }
+        result
     }
 
     /// Adds a node to the tree with an optional parent.
@@ -620,4 +717,30 @@ mod tests {
         assert!(prefix3.contains("└"));
         assert!(!prefix3.contains("├"));
     }
+
+    #[cfg(feature = "stats")]
+    #[test]
+    fn test_incremental_stats() {
+        let mut tree = IncrementalTree::new();
+        assert_eq!(tree.stats().node_count, 0);
+
+        let root_id = tree.add_node("root", None);
+        assert_eq!(tree.stats(), tree.build_tree().unwrap().stats());
+
+        let dir_id = tree.add_node("dir", Some(root_id));
+        tree.add_leaf("a", Some(dir_id));
+        tree.add_leaf_lines(vec!["b1", "b2"], Some(dir_id));
+        tree.add_leaf("c", Some(dir_id));
+        let nested_id = tree.add_node("nested", Some(dir_id));
+        tree.add_leaf("deep", Some(nested_id));
+        tree.add_leaf("top", Some(root_id));
+        assert_eq!(tree.stats(), tree.build_tree().unwrap().stats());
+
+        let other_id = tree.add_node("other", None);
+        tree.add_leaf("x", Some(other_id));
+        tree.add_leaf("loose", None);
+        assert_eq!(tree.stats(), tree.build_tree().unwrap().stats());
+        assert_eq!(tree.stats().node_count, 5);
+        assert_eq!(tree.stats().total_lines, 8);
+    }
 }
@@ -56,6 +56,10 @@ struct LeafState {
 /// the entire tree string in memory. Each iteration yields a [`Line`] struct
 /// containing the prefix, content, depth, and last-child status.
 ///
+/// Lines are yielded in the same order as [`Tree::pre_order`](crate::tree::Tree)
+/// visits elements (with the `traversal` feature): each node yields one line for
+/// its label, each leaf one line per text line, and empty leaves yield nothing.
+///
 /// For incrementally-built trees, see [`IncrementalTree`](crate::incremental::IncrementalTree)
 /// instead.
 ///
@@ -221,9 +225,9 @@ impl<'a> Iterator for TreeLines<'a> {
 
                     match child {
                         Tree::Node(label, grand_children) => {
-                            let prefix = Self::build_prefix(&level, &self.config.style);
+                            let prefix = Self::build_prefix(&new_level, &self.config.style);
                             let formatted = self.config.format_node(label);
-                            let depth = level.len();
+                            let depth = new_level.len();
 
                             // Push remaining siblings
                             if child_idx + 1 < children.len() {
@@ -243,14 +247,18 @@ impl<'a> Iterator for TreeLines<'a> {
                         }
                         Tree::Leaf(lines) => {
                             if lines.is_empty() {
+                                // Empty leaves render nothing, but their siblings still do
+                                if child_idx + 1 < children.len() {
+                                    self.stack.push((child_idx + 1, parent, level));
+                                }
                                 continue;
                             }
 
-                            let prefix = Self::build_prefix(&level, &self.config.style);
+                            let prefix = Self::build_prefix(&new_level, &self.config.style);
                             let second_prefix =
-                                Self::build_second_line_prefix(&level, &self.config.style);
+                                Self::build_second_line_prefix(&new_level, &self.config.style);
 
-                            let depth = level.len();
+                            let depth = new_level.len();
                             if lines.len() == 1 {
                                 // Single line leaf - yield immediately
                                 let formatted = self.config.format_leaf(&lines[0]);
@@ -271,7 +279,7 @@ impl<'a> Iterator for TreeLines<'a> {
                                     index: 0,
                                     prefix: prefix.clone(),
                                     second_line_prefix: second_prefix,
-                                    level: level.clone(),
+                                    level: new_level,
                                 });
                                 // Push remaining siblings
                                 if child_idx + 1 < children.len() {
@@ -364,11 +372,51 @@ impl TreeIteratorExt for Tree {
 
     fn to_lines_with_config(&self, config: &RenderConfig) -> Vec<String> {
         TreeIteratorExt::lines_with_config(self, config)
-            .map(|line| format!("{}{}", line.prefix, line.content))
+            .map(|line| {
+                let line = format!("{}{}", line.prefix, line.content);
+                if config.trim_trailing {
+                    line.trim_end().to_string()
+                } else {
+                    line
+                }
+            })
             .collect()
     }
 }
 
+impl Tree {
+    /// Renders at most `max_lines` lines of the tree, appending a truncation notice
+    /// if more lines remain.
+    ///
+    /// The lines are exactly the first lines of
+    /// [`render_to_string_with_config`](Tree::render_to_string_with_config), with every
+    /// option of `config` applied, and the notice ends with the configured line
+    /// ending. Rendering stops once the limit is reached, so the rest of the tree
+    /// is never rendered; blank lines between top-level children count as lines.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::{Tree, RenderConfig};
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["a".to_string()]),
+    ///     Tree::Leaf(vec!["b".to_string()])
+    /// ]);
+    /// let preview = tree.render_preview(2, &RenderConfig::default());
+    /// assert_eq!(preview, "root\n├─ a\n… (rendering truncated)\n");
+    /// ```
+    pub fn render_preview(&self, max_lines: usize, config: &RenderConfig) -> String {
+        let (mut output, truncated) =
+            crate::renderer::render_to_string_limited(self, config, max_lines);
+        if truncated {
+            output.push_str("… (rendering truncated)");
+            output.push_str(&config.line_ending);
+        }
+        output
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -392,4 +440,161 @@ mod tests {
         let lines = tree.to_lines();
         assert!(!lines.is_empty());
     }
+
+    #[test]
+    fn test_empty_leaf_keeps_siblings() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![Tree::Leaf(vec![]), Tree::Leaf(vec!["b".to_string()])],
+        );
+        assert_eq!(tree.to_lines(), vec!["root", "└─ b"]);
+    }
+
+    #[cfg(feature = "traversal")]
+    #[test]
+    fn test_pre_order_matches_lines() {
+        let leaf = |text: &str| Tree::Leaf(vec![text.to_string()]);
+        let trees = vec![
+            leaf("single"),
+            Tree::Leaf(vec!["a".to_string(), "b".to_string(), "c".to_string()]),
+            Tree::Node("empty".to_string(), vec![]),
+            Tree::Node(
+                "root".to_string(),
+                vec![
+                    Tree::Node(
+                        "dir".to_string(),
+                        vec![
+                            leaf("a"),
+                            Tree::Leaf(vec!["multi".to_string(), "line".to_string()]),
+                            Tree::Node("nested".to_string(), vec![leaf("deep")]),
+                        ],
+                    ),
+                    Tree::Node("childless".to_string(), vec![]),
+                    Tree::Leaf(vec![]),
+                    leaf("last"),
+                ],
+            ),
+            Tree::Node(
+                "root".to_string(),
+                vec![
+                    Tree::Node("x".to_string(), vec![Tree::Leaf(vec![])]),
+                    leaf("y"),
+                ],
+            ),
+        ];
+
+        for tree in &trees {
+            let expected: Vec<&str> = tree
+                .pre_order()
+                .flat_map(|element| match element {
+                    Tree::Node(label, _) => vec![label.as_str()],
+                    Tree::Leaf(lines) => lines.iter().map(String::as_str).collect(),
+                })
+                .collect();
+            let actual: Vec<String> = TreeIteratorExt::lines(tree)
+                .map(|line| line.content)
+                .collect();
+            assert_eq!(actual, expected, "order mismatch for {tree:?}");
+            assert_eq!(
+                tree.to_lines().join("\n") + "\n",
+                tree.render_to_string(),
+                "line mismatch for {tree:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_root_multi_line_leaf() {
+        let tree = Tree::Leaf(vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+        assert_eq!(tree.to_lines(), vec!["a", "b", "c"]);
+        assert!(TreeIteratorExt::lines(&tree).all(|line| line.prefix.is_empty()));
+    }
+
+    #[test]
+    fn test_multi_line_middle_leaf_guides() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node(
+                    "a".to_string(),
+                    vec![
+                        Tree::Leaf(vec!["x1".to_string(), "x2".to_string()]),
+                        Tree::Leaf(vec!["y".to_string()]),
+                    ],
+                ),
+                Tree::Leaf(vec!["b".to_string()]),
+            ],
+        );
+        assert_eq!(
+            tree.to_lines(),
+            vec!["root", "├─ a", "│  ├─ x1", "│  │  x2", "│  └─ y", "└─ b"]
+        );
+    }
+
+    #[test]
+    fn test_render_preview() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            (0..100)
+                .map(|i| {
+                    Tree::Node(
+                        format!("dir{i}"),
+                        vec![Tree::Leaf(vec![format!("file{i}")])],
+                    )
+                })
+                .collect(),
+        );
+        let config = RenderConfig::default();
+        let preview = tree.render_preview(10, &config);
+        let lines: Vec<&str> = preview.lines().collect();
+        assert_eq!(lines.len(), 11);
+        assert_eq!(lines[..10], tree.to_lines()[..10]);
+        assert_eq!(lines[10], "… (rendering truncated)");
+
+        assert_eq!(
+            tree.render_preview(201, &config),
+            tree.render_to_string_with_config(&config)
+        );
+        assert_eq!(tree.render_preview(0, &config), "… (rendering truncated)\n");
+    }
+
+    #[test]
+    fn test_render_preview_matches_configured_render() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node(
+                    "flat".to_string(),
+                    vec![
+                        Tree::Leaf(vec!["a".to_string()]),
+                        Tree::Leaf(vec!["b".to_string()]),
+                    ],
+                ),
+                Tree::Node(
+                    "hidden".to_string(),
+                    vec![Tree::Leaf(vec!["secret".to_string()])],
+                ),
+                Tree::Leaf(vec!["key = 1".to_string(), "more".to_string()]),
+                Tree::Leaf(vec!["longer_key = 2".to_string()]),
+            ],
+        );
+        let config = RenderConfig::default()
+            .with_line_ending("\r\n")
+            .with_child_counts(true)
+            .with_collapsed(vec![1])
+            .with_leaf_bullet("- ")
+            .with_join_leaf_siblings(", ")
+            .with_align_on('=')
+            .with_blank_line_between_top_level(true);
+        let full = tree.render_to_string_with_config(&config);
+        let full_lines: Vec<&str> = full.split_inclusive('\n').collect();
+        assert!(full.contains("a, b") && !full.contains("secret"));
+
+        for max_lines in 0..full_lines.len() {
+            let preview = tree.render_preview(max_lines, &config);
+            let expected = full_lines[..max_lines].concat() + "… (rendering truncated)\r\n";
+            assert_eq!(preview, expected);
+        }
+        assert_eq!(tree.render_preview(full_lines.len(), &config), full);
+    }
 }
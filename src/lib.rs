@@ -5,6 +5,8 @@
     feature = "arbitrary-yaml",
     feature = "arbitrary-toml",
     feature = "arbitrary-xml",
+    feature = "arbitrary-csv",
+    feature = "arbitrary-env",
     feature = "arbitrary-walkdir",
     feature = "arbitrary-git2",
     feature = "arbitrary-syn",
@@ -15,6 +17,8 @@
     doc
 ))]
 pub mod arbitrary;
+#[cfg(any(feature = "binary", doc))]
+pub mod binary;
 #[cfg(any(feature = "builder", doc))]
 pub mod builder;
 #[cfg(any(feature = "compare", doc))]
@@ -22,6 +26,8 @@ pub mod compare;
 pub mod config;
 #[cfg(any(feature = "export", doc))]
 pub mod export;
+#[cfg(any(feature = "hash", doc))]
+pub mod hash;
 #[cfg(any(feature = "incremental", doc))]
 pub mod incremental;
 #[cfg(any(feature = "iterator", doc))]
@@ -58,18 +64,21 @@ pub mod tree;
 pub mod utils;
 
 // Re-export main types
-pub use config::RenderConfig;
+pub use config::{CountMode, RenderConfig, Severity};
 #[cfg(any(feature = "iterator", doc))]
 pub use iterator::{Line, TreeIteratorExt};
 pub use level::LevelPath;
+#[cfg(any(feature = "search", doc))]
+pub use search::SearchOptions;
 #[cfg(any(feature = "stats", doc))]
 pub use stats::TreeStats;
 pub use style::{StyleConfig, TreeStyle};
-pub use tree::Tree;
+pub use tree::{RenderIssue, Tree};
 
 // Re-export renderer functions
 pub use renderer::{
-    render_to_string, render_to_string_with_config, write_tree, write_tree_with_config,
+    DebugTree, render_forest, render_to_string, render_to_string_with_config, write_tree,
+    write_tree_with_config,
 };
 
 // Re-export prefix functions
@@ -106,6 +115,23 @@ impl Tree {
         render_to_string_with_config(self, config)
     }
 
+    /// Returns a wrapper whose `Debug` output is the rendered tree.
+    ///
+    /// The derived `Debug` of `Tree` is kept for machine-oriented output; use
+    /// this for readable `dbg!` output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![Tree::Leaf(vec!["item".to_string()])]);
+    /// dbg!(tree.debug_tree());
+    /// ```
+    pub fn debug_tree(&self) -> DebugTree<'_> {
+        DebugTree(self)
+    }
+
     /// Renders this tree to a writer using the default configuration.
     ///
     /// # Examples
@@ -130,6 +156,75 @@ impl Tree {
     ) -> std::fmt::Result {
         write_tree_with_config(writer, self, config)
     }
+
+    /// Renders the subtree at `path` as the root, preceded by a breadcrumb of its ancestors.
+    ///
+    /// The breadcrumb line joins the labels of the ancestors, from the root down,
+    /// with ` > `. Focusing the root (an empty path) renders no breadcrumb.
+    /// Returns `None` if no element exists at `path`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::{Tree, RenderConfig};
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Node("src".to_string(), vec![
+    ///         Tree::Leaf(vec!["main.rs".to_string()])
+    ///     ])
+    /// ]);
+    /// let focused = tree.render_focused(&[0], &RenderConfig::default()).unwrap();
+    /// assert_eq!(focused, "root\nsrc\n└─ main.rs\n");
+    /// ```
+    pub fn render_focused(&self, path: &[usize], config: &RenderConfig) -> Option<String> {
+        let mut ancestors = Vec::with_capacity(path.len());
+        let mut focus = self;
+        for &index in path {
+            match focus {
+                Tree::Node(label, children) => {
+                    ancestors.push(label.as_str());
+                    focus = children.get(index)?;
+                }
+                Tree::Leaf(_) => return None,
+            }
+        }
+        let mut output = String::new();
+        if !ancestors.is_empty() {
+            output.push_str(&ancestors.join(" > "));
+            output.push_str(&config.line_ending);
+        }
+        output.push_str(&render_to_string_with_config(focus, config));
+        Some(output)
+    }
+
+    /// Renders the tree with annotations appended to individual lines.
+    ///
+    /// `annotations` maps child-index paths to text that is appended, after
+    /// [`annotation_gutter`](RenderConfig::annotation_gutter) spaces, to the line
+    /// of the node at that path, or to the first line of the leaf at that path.
+    /// Paths without an element are ignored, and the tree itself is unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use treelog::{RenderConfig, Tree};
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["a.rs".to_string()]),
+    ///     Tree::Leaf(vec!["b.rs".to_string()])
+    /// ]);
+    /// let annotations = HashMap::from([(vec![1], "modified".to_string())]);
+    /// let output = tree.render_with_annotations(&annotations, &RenderConfig::default());
+    /// assert_eq!(output, "root\n├─ a.rs\n└─ b.rs  modified\n");
+    /// ```
+    pub fn render_with_annotations(
+        &self,
+        annotations: &std::collections::HashMap<Vec<usize>, String>,
+        config: &RenderConfig,
+    ) -> String {
+        renderer::render_to_string_with_annotations(self, annotations, config)
+    }
 }
 
 #[cfg(test)]
@@ -147,6 +242,88 @@ mod tests {
         assert!(output.contains("item"));
     }
 
+    #[test]
+    fn test_render_focused() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Leaf(vec!["other".to_string()]),
+                Tree::Node(
+                    "a".to_string(),
+                    vec![Tree::Node(
+                        "b".to_string(),
+                        vec![Tree::Node(
+                            "c".to_string(),
+                            vec![Tree::Leaf(vec!["deep".to_string()])],
+                        )],
+                    )],
+                ),
+            ],
+        );
+        let config = RenderConfig::default();
+        assert_eq!(
+            tree.render_focused(&[1, 0, 0], &config).unwrap(),
+            "root > a > b\nc\n└─ deep\n"
+        );
+        assert_eq!(
+            tree.render_focused(&[1, 0, 0, 0], &config).unwrap(),
+            "root > a > b > c\ndeep\n"
+        );
+        assert_eq!(
+            tree.render_focused(&[], &config).unwrap(),
+            tree.render_to_string()
+        );
+        assert_eq!(tree.render_focused(&[2], &config), None);
+        assert_eq!(tree.render_focused(&[0, 0], &config), None);
+    }
+
+    #[test]
+    fn test_debug_tree() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node("child".to_string(), vec![Tree::Leaf(vec!["a".to_string()])]),
+                Tree::Leaf(vec!["b".to_string(), "c".to_string()]),
+            ],
+        );
+        assert_eq!(format!("{:?}", tree.debug_tree()), tree.render_to_string());
+        assert_eq!(format!("{:#?}", tree.debug_tree()), tree.render_to_string());
+        assert!(format!("{:?}", tree).starts_with("Node(\"root\""));
+    }
+
+    #[test]
+    fn test_render_with_annotations() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node(
+                    "src".to_string(),
+                    vec![Tree::Leaf(vec!["main.rs".to_string(), "more".to_string()])],
+                ),
+                Tree::Leaf(vec!["README".to_string()]),
+            ],
+        );
+        let annotations = std::collections::HashMap::from([
+            (vec![0], "3 files".to_string()),
+            (vec![0, 0], "+12 -4".to_string()),
+            (vec![5], "missing".to_string()),
+        ]);
+        assert_eq!(
+            tree.render_with_annotations(&annotations, &RenderConfig::default()),
+            "root\n├─ src  3 files\n│  └─ main.rs  +12 -4\n│     more\n└─ README\n"
+        );
+
+        let config = RenderConfig::default().with_annotation_gutter(1);
+        assert_eq!(
+            tree.render_with_annotations(&annotations, &config),
+            "root\n├─ src 3 files\n│  └─ main.rs +12 -4\n│     more\n└─ README\n"
+        );
+        assert_eq!(
+            tree.render_with_annotations(&Default::default(), &config),
+            tree.render_to_string()
+        );
+    }
+
     #[cfg(feature = "builder")]
     #[test]
     fn test_builder_api() {
@@ -21,8 +21,8 @@
 //! ```
 //!
 //! The macro supports:
-//! - **Nodes**: `identifier { ... }` or `"string" { ... }`
-//! - **Leaves**: `"string"` or bare identifiers (treated as strings)
+//! - **Nodes**: `identifier { ... }`, `"string" { ... }` or `(expression) { ... }`
+//! - **Leaves**: `"string"`, bare identifiers (treated as strings) or `(expression)`
 //! - **Nested structures**: Arbitrary nesting depth
 //! - **Comma-separated**: Children separated by commas (trailing comma optional)
 
@@ -33,8 +33,9 @@
 ///
 /// # Syntax
 ///
-/// - **Nodes**: `name { children... }` or `"name" { children... }`
-/// - **Leaves**: `"text"` or bare identifiers (converted to strings)
+/// - **Nodes**: `name { children... }`, `"name" { children... }` or `(expr) { children... }`
+/// - **Leaves**: `"text"`, bare identifiers (converted to strings) or `(expr)`
+/// - **Expressions**: Parenthesized expressions are converted with `to_string()`
 /// - **Children**: Comma-separated list (trailing comma optional)
 ///
 /// # Examples
@@ -106,6 +107,24 @@
 /// };
 /// # }
 /// ```
+///
+/// Runtime values as labels and leaves:
+/// ```
+/// # #[cfg(feature = "macro")]
+/// # {
+/// use treelog::tree;
+///
+/// let name = String::from("crate");
+/// let version = 3;
+/// let tree = tree! {
+///     (name) {
+///         (format!("v{version}")),
+///         "static"
+///     }
+/// };
+/// assert_eq!(tree.label(), Some("crate"));
+/// # }
+/// ```
 #[macro_export]
 macro_rules! tree {
     // Entry point: single node with identifier
@@ -123,6 +142,14 @@ macro_rules! tree {
             $crate::__tree_parse_children!([$($children)*] [])
         )
     };
+
+    // Entry point: single node with parenthesized expression
+    (($name:expr) { $($children:tt)* }) => {
+        $crate::Tree::Node(
+            $name.to_string(),
+            $crate::__tree_parse_children!([$($children)*] [])
+        )
+    };
 }
 
 #[macro_export]
@@ -138,6 +165,11 @@ macro_rules! __tree_parse_children {
         $crate::__tree_parse_children!([$($rest)*] [$($acc,)* $crate::tree!($l { $($s)* }),])
     };
 
+    // Node with expression followed by comma
+    ([($e:expr) { $($s:tt)* } , $($rest:tt)*] [$($acc:expr,)*]) => {
+        $crate::__tree_parse_children!([$($rest)*] [$($acc,)* $crate::tree!(($e) { $($s)* }),])
+    };
+
     // Leaf with string followed by comma
     ([$l:literal , $($rest:tt)*] [$($acc:expr,)*]) => {
         $crate::__tree_parse_children!([$($rest)*] [$($acc,)* $crate::Tree::Leaf(vec![$l.to_string()]),])
@@ -148,6 +180,11 @@ macro_rules! __tree_parse_children {
         $crate::__tree_parse_children!([$($rest)*] [$($acc,)* $crate::Tree::Leaf(vec![stringify!($i).to_string()]),])
     };
 
+    // Leaf with expression followed by comma
+    ([($e:expr) , $($rest:tt)*] [$($acc:expr,)*]) => {
+        $crate::__tree_parse_children!([$($rest)*] [$($acc,)* $crate::Tree::Leaf(vec![$e.to_string()]),])
+    };
+
     // Last: node with identifier
     ([$i:ident { $($s:tt)* }] [$($acc:expr,)*]) => {
         vec![$($acc,)* $crate::tree!($i { $($s)* })]
@@ -158,6 +195,11 @@ macro_rules! __tree_parse_children {
         vec![$($acc,)* $crate::tree!($l { $($s)* })]
     };
 
+    // Last: node with expression
+    ([($e:expr) { $($s:tt)* }] [$($acc:expr,)*]) => {
+        vec![$($acc,)* $crate::tree!(($e) { $($s)* })]
+    };
+
     // Last: leaf with string
     ([$l:literal] [$($acc:expr,)*]) => {
         vec![$($acc,)* $crate::Tree::Leaf(vec![$l.to_string()])]
@@ -168,6 +210,11 @@ macro_rules! __tree_parse_children {
         vec![$($acc,)* $crate::Tree::Leaf(vec![stringify!($i).to_string()])]
     };
 
+    // Last: leaf with expression
+    ([($e:expr)] [$($acc:expr,)*]) => {
+        vec![$($acc,)* $crate::Tree::Leaf(vec![$e.to_string()])]
+    };
+
     // Base case: empty input (with or without trailing comma), return accumulated
     ([] [$($acc:expr,)*]) => {
         vec![$($acc,)*]
@@ -327,4 +374,38 @@ mod tests {
             assert_eq!(children.len(), 3);
         }
     }
+
+    #[test]
+    fn test_expression_labels() {
+        let name = String::from("dynamic");
+        let child = "child";
+        let count = 2;
+        let tree = tree! {
+            (name) {
+                (child) {
+                    (format!("{count} items")),
+                    "static"
+                },
+                (count)
+            }
+        };
+
+        assert_eq!(
+            tree,
+            Tree::Node(
+                "dynamic".to_string(),
+                vec![
+                    Tree::Node(
+                        "child".to_string(),
+                        vec![
+                            Tree::Leaf(vec!["2 items".to_string()]),
+                            Tree::Leaf(vec!["static".to_string()]),
+                        ],
+                    ),
+                    Tree::Leaf(vec!["2".to_string()]),
+                ],
+            )
+        );
+        assert_eq!(name, "dynamic");
+    }
 }
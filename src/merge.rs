@@ -1,5 +1,7 @@
 //! Tree merging operations.
 
+use std::collections::HashMap;
+
 use crate::tree::Tree;
 
 #[cfg(feature = "clap")]
@@ -18,6 +20,17 @@ pub enum MergeStrategy {
     MergeByLabel,
 }
 
+/// Which of the merged trees an element of the result came from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Provenance {
+    /// Taken from the first tree only
+    First,
+    /// Taken from the second tree only
+    Second,
+    /// Present in both trees, or combined from both
+    Both,
+}
+
 impl Tree {
     /// Merges this tree with another tree using the specified strategy.
     ///
@@ -45,6 +58,194 @@ impl Tree {
         }
     }
 
+    /// Merges this tree with another position by position, marking conflicts inline.
+    ///
+    /// Nodes with equal labels have their children merged pairwise by index, and
+    /// children present on only one side are kept as they are. Where two leaves
+    /// differ, the result is a single leaf holding both versions between
+    /// `<<<< ours`, `==== theirs` and `>>>>` marker lines. Where node labels
+    /// differ, the label becomes `<<<< ours ==== theirs >>>>` and the children
+    /// are still merged. Where a node meets a leaf, a `<<<< conflict >>>>` node
+    /// keeps both sides, under `<<<< ours` and `==== theirs` nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let ours = Tree::Node("config".to_string(), vec![
+    ///     Tree::Leaf(vec!["debug = true".to_string()])
+    /// ]);
+    /// let theirs = Tree::Node("config".to_string(), vec![
+    ///     Tree::Leaf(vec!["debug = false".to_string()])
+    /// ]);
+    /// assert_eq!(ours.merge_annotated(&theirs), Tree::Node("config".to_string(), vec![
+    ///     Tree::Leaf(vec![
+    ///         "<<<< ours".to_string(),
+    ///         "debug = true".to_string(),
+    ///         "==== theirs".to_string(),
+    ///         "debug = false".to_string(),
+    ///         ">>>>".to_string(),
+    ///     ])
+    /// ]));
+    /// ```
+    pub fn merge_annotated(&self, other: &Tree) -> Tree {
+        match (self, other) {
+            (Tree::Node(label1, children1), Tree::Node(label2, children2)) => {
+                let label = if label1 == label2 {
+                    label1.clone()
+                } else {
+                    format!("<<<< {label1} ==== {label2} >>>>")
+                };
+                let shared = children1.len().min(children2.len());
+                let mut merged_children: Vec<Tree> = children1
+                    .iter()
+                    .zip(children2.iter())
+                    .map(|(child1, child2)| child1.merge_annotated(child2))
+                    .collect();
+                merged_children.extend(children1[shared..].iter().cloned());
+                merged_children.extend(children2[shared..].iter().cloned());
+                Tree::Node(label, merged_children)
+            }
+            (Tree::Leaf(lines1), Tree::Leaf(lines2)) if lines1 == lines2 => self.clone(),
+            (Tree::Leaf(lines1), Tree::Leaf(lines2)) => {
+                let mut lines = Vec::with_capacity(lines1.len() + lines2.len() + 3);
+                lines.push("<<<< ours".to_string());
+                lines.extend(lines1.iter().cloned());
+                lines.push("==== theirs".to_string());
+                lines.extend(lines2.iter().cloned());
+                lines.push(">>>>".to_string());
+                Tree::Leaf(lines)
+            }
+            _ => Tree::Node(
+                "<<<< conflict >>>>".to_string(),
+                vec![
+                    self.clone().under("<<<< ours"),
+                    other.clone().under("==== theirs"),
+                ],
+            ),
+        }
+    }
+
+    /// Merges this tree with another like [`merge`](Self::merge), also recording where
+    /// each element of the result came from.
+    ///
+    /// The map has an entry for every element of the merged tree, keyed by its path
+    /// (child indices from the root). Nodes whose children were combined from both
+    /// trees, and leaves that are equal in both, are marked [`Provenance::Both`];
+    /// everything else is marked with the tree it was copied from.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::{Tree, merge::{MergeStrategy, Provenance}};
+    ///
+    /// let tree1 = Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["a".to_string()])
+    /// ]);
+    /// let tree2 = Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["b".to_string()])
+    /// ]);
+    /// let (merged, provenance) = tree1.merge_with_provenance(&tree2, MergeStrategy::Append);
+    /// assert_eq!(provenance[&vec![]], Provenance::Both);
+    /// assert_eq!(provenance[&vec![0]], Provenance::First);
+    /// assert_eq!(provenance[&vec![1]], Provenance::Second);
+    /// ```
+    pub fn merge_with_provenance(
+        &self,
+        other: &Tree,
+        strategy: MergeStrategy,
+    ) -> (Tree, HashMap<Vec<usize>, Provenance>) {
+        let mut provenance = HashMap::new();
+        let merged = self.merge_tracked(other, &strategy, &mut Vec::new(), &mut provenance);
+        (merged, provenance)
+    }
+
+    /// Merges like the strategy's own method, recording provenance below `path`.
+    fn merge_tracked(
+        &self,
+        other: &Tree,
+        strategy: &MergeStrategy,
+        path: &mut Vec<usize>,
+        provenance: &mut HashMap<Vec<usize>, Provenance>,
+    ) -> Tree {
+        match (strategy, self, other) {
+            (MergeStrategy::Replace, _, _) => {
+                other.copied_from(Provenance::Second, path, provenance)
+            }
+            (_, Tree::Leaf(lines1), Tree::Leaf(lines2)) => {
+                let origin = if lines1 == lines2 {
+                    Provenance::Both
+                } else {
+                    Provenance::Second
+                };
+                other.copied_from(origin, path, provenance)
+            }
+            (_, Tree::Node(..), Tree::Leaf(_)) => {
+                self.copied_from(Provenance::First, path, provenance)
+            }
+            (_, Tree::Leaf(_), Tree::Node(..)) => {
+                other.copied_from(Provenance::Second, path, provenance)
+            }
+            (_, Tree::Node(label1, children1), Tree::Node(label2, children2)) => {
+                provenance.insert(path.clone(), Provenance::Both);
+                let by_label = *strategy == MergeStrategy::MergeByLabel && label1 == label2;
+                let mut used_indices = std::collections::HashSet::new();
+                let mut merged_children = Vec::with_capacity(children1.len() + children2.len());
+                for child1 in children1 {
+                    path.push(merged_children.len());
+                    let matching = match child1 {
+                        Tree::Node(child_label, _) if by_label => {
+                            children2.iter().enumerate().find(|(i, c)| {
+                                !used_indices.contains(i)
+                                    && matches!(c, Tree::Node(l, _) if l == child_label)
+                            })
+                        }
+                        _ => None,
+                    };
+                    let merged = if let Some((index, child2)) = matching {
+                        used_indices.insert(index);
+                        child1.merge_tracked(child2, strategy, path, provenance)
+                    } else {
+                        child1.copied_from(Provenance::First, path, provenance)
+                    };
+                    path.pop();
+                    merged_children.push(merged);
+                }
+                for (index, child2) in children2.iter().enumerate() {
+                    if !used_indices.contains(&index) {
+                        path.push(merged_children.len());
+                        merged_children.push(child2.copied_from(
+                            Provenance::Second,
+                            path,
+                            provenance,
+                        ));
+                        path.pop();
+                    }
+                }
+                Tree::Node(label1.clone(), merged_children)
+            }
+        }
+    }
+
+    /// Clones this subtree, marking all of it as coming from `origin`.
+    fn copied_from(
+        &self,
+        origin: Provenance,
+        path: &mut Vec<usize>,
+        provenance: &mut HashMap<Vec<usize>, Provenance>,
+    ) -> Tree {
+        provenance.insert(path.clone(), origin);
+        if let Tree::Node(_, children) = self {
+            for (index, child) in children.iter().enumerate() {
+                path.push(index);
+                child.copied_from(origin, path, provenance);
+                path.pop();
+            }
+        }
+        self.clone()
+    }
+
     fn merge_replace(&self, other: &Tree) -> Tree {
         // Simply return a clone of the other tree
         other.clone()
@@ -161,4 +362,124 @@ mod tests {
             assert_eq!(children.len(), 1);
         }
     }
+
+    #[test]
+    fn test_merge_with_provenance() {
+        let tree1 = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node(
+                    "shared".to_string(),
+                    vec![Tree::Leaf(vec!["a".to_string()])],
+                ),
+                Tree::Leaf(vec!["mine".to_string()]),
+            ],
+        );
+        let tree2 = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node(
+                    "shared".to_string(),
+                    vec![Tree::Leaf(vec!["b".to_string()])],
+                ),
+                Tree::Node(
+                    "theirs".to_string(),
+                    vec![Tree::Leaf(vec!["c".to_string()])],
+                ),
+            ],
+        );
+
+        let (merged, provenance) = tree1.merge_with_provenance(&tree2, MergeStrategy::MergeByLabel);
+        assert_eq!(
+            merged,
+            tree1.merge(tree2.clone(), MergeStrategy::MergeByLabel)
+        );
+        assert_eq!(provenance.len(), 7);
+        assert_eq!(provenance[&vec![]], Provenance::Both);
+        assert_eq!(provenance[&vec![0]], Provenance::Both);
+        assert_eq!(provenance[&vec![0, 0]], Provenance::First);
+        assert_eq!(provenance[&vec![0, 1]], Provenance::Second);
+        assert_eq!(provenance[&vec![1]], Provenance::First);
+        if let Tree::Node(_, children) = &merged {
+            assert_eq!(children[2].label(), Some("theirs"));
+        }
+        assert_eq!(provenance[&vec![2]], Provenance::Second);
+        assert_eq!(provenance[&vec![2, 0]], Provenance::Second);
+
+        fn count(tree: &Tree) -> usize {
+            match tree {
+                Tree::Node(_, children) => 1 + children.iter().map(count).sum::<usize>(),
+                Tree::Leaf(_) => 1,
+            }
+        }
+        for strategy in [MergeStrategy::Replace, MergeStrategy::Append] {
+            let (merged, provenance) = tree1.merge_with_provenance(&tree2, strategy.clone());
+            assert_eq!(merged, tree1.merge(tree2.clone(), strategy));
+            assert_eq!(provenance.len(), count(&merged));
+        }
+    }
+
+    #[test]
+    fn test_merge_annotated() {
+        let ours = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Leaf(vec!["same".to_string()]),
+                Tree::Node(
+                    "dir".to_string(),
+                    vec![Tree::Leaf(vec!["version = 1".to_string()])],
+                ),
+            ],
+        );
+        let theirs = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Leaf(vec!["same".to_string()]),
+                Tree::Node(
+                    "dir".to_string(),
+                    vec![Tree::Leaf(vec!["version = 2".to_string()])],
+                ),
+                Tree::Leaf(vec!["added".to_string()]),
+            ],
+        );
+        let merged = ours.merge_annotated(&theirs);
+        assert_eq!(
+            merged,
+            Tree::Node(
+                "root".to_string(),
+                vec![
+                    Tree::Leaf(vec!["same".to_string()]),
+                    Tree::Node(
+                        "dir".to_string(),
+                        vec![Tree::Leaf(vec![
+                            "<<<< ours".to_string(),
+                            "version = 1".to_string(),
+                            "==== theirs".to_string(),
+                            "version = 2".to_string(),
+                            ">>>>".to_string(),
+                        ])],
+                    ),
+                    Tree::Leaf(vec!["added".to_string()]),
+                ],
+            )
+        );
+        assert_eq!(ours.merge_annotated(&ours), ours);
+    }
+
+    #[test]
+    fn test_merge_annotated_kind_conflict() {
+        let node = Tree::Node("a".to_string(), vec![]);
+        let leaf = Tree::Leaf(vec!["a".to_string()]);
+        assert_eq!(
+            node.merge_annotated(&leaf),
+            Tree::Node(
+                "<<<< conflict >>>>".to_string(),
+                vec![node.clone().under("<<<< ours"), leaf.under("==== theirs")],
+            )
+        );
+        assert_eq!(
+            node.merge_annotated(&Tree::Node("b".to_string(), vec![])),
+            Tree::Node("<<<< a ==== b >>>>".to_string(), vec![])
+        );
+    }
 }
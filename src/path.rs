@@ -1,12 +1,55 @@
 //! Tree path utilities for navigating and accessing tree elements by path.
 
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
 use crate::tree::Tree;
 
 /// Represents a path through a tree as a sequence of child indices.
 pub type TreePath = Vec<usize>;
 
+/// Errors returned by path-based tree operations.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PathError {
+    /// No element exists at the given path
+    NotFound(TreePath),
+    /// The element at the given path is a leaf, not a node
+    NotANode(TreePath),
+    /// A child index is out of range for the node at the given path
+    IndexOutOfBounds {
+        path: TreePath,
+        index: usize,
+        len: usize,
+    },
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathError::NotFound(path) => write!(f, "no element at path {path:?}"),
+            PathError::NotANode(path) => write!(f, "element at path {path:?} is not a node"),
+            PathError::IndexOutOfBounds { path, index, len } => write!(
+                f,
+                "child index {index} out of range for node at path {path:?} with {len} children"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PathError {}
+
 /// Represents a flattened tree entry with its path and content.
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    any(
+        feature = "serde",
+        feature = "serde-json",
+        feature = "serde-yaml",
+        feature = "serde-toml",
+        feature = "serde-ron"
+    ),
+    derive(serde::Serialize, serde::Deserialize)
+)]
 pub struct FlattenedEntry {
     /// The path from root to this entry
     pub path: TreePath,
@@ -14,8 +57,74 @@ pub struct FlattenedEntry {
     pub content: String,
     /// Whether this entry is a node
     pub is_node: bool,
+    /// Index of the parent entry in the flattened list (`None` for the root)
+    pub parent: Option<usize>,
+    /// All lines of a leaf (empty for nodes)
+    pub lines: Vec<String>,
+}
+
+/// A tree flattened into pre-order records that reference their parent by index.
+///
+/// Serializes as a plain list of [`FlattenedEntry`] records, which suits
+/// columnar and tabular pipelines.
+///
+/// # Examples
+///
+/// ```
+/// use treelog::Tree;
+///
+/// let tree = Tree::Node("root".to_string(), vec![
+///     Tree::Leaf(vec!["item".to_string()])
+/// ]);
+/// let flat = tree.to_flat();
+/// assert_eq!(flat.entries[1].parent, Some(0));
+/// assert_eq!(Tree::from_flat(&flat).unwrap(), tree);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    any(
+        feature = "serde",
+        feature = "serde-json",
+        feature = "serde-yaml",
+        feature = "serde-toml",
+        feature = "serde-ron"
+    ),
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct FlatTree {
+    /// The entries in pre-order, starting with the root
+    pub entries: Vec<FlattenedEntry>,
+}
+
+/// Errors returned when rebuilding a tree from a [`FlatTree`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FlatTreeError {
+    /// The flat tree has no entries
+    Empty,
+    /// An entry other than the first has no parent
+    MultipleRoots(usize),
+    /// An entry's parent index does not refer to an earlier node entry
+    InvalidParent { index: usize, parent: usize },
 }
 
+impl fmt::Display for FlatTreeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FlatTreeError::Empty => write!(f, "flat tree has no entries"),
+            FlatTreeError::MultipleRoots(index) => {
+                write!(f, "entry {index} has no parent but is not the first entry")
+            }
+            FlatTreeError::InvalidParent { index, parent } => write!(
+                f,
+                "entry {index} has parent {parent}, which is not an earlier node entry"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FlatTreeError {}
+
 impl Tree {
     /// Gets the path from the root to this specific tree node.
     ///
@@ -116,6 +225,146 @@ impl Tree {
         None
     }
 
+    /// Swaps two children of the node at `parent_path`.
+    ///
+    /// Returns an error if `parent_path` does not exist, points to a leaf, or
+    /// either index is out of range. Swapping a child with itself is a no-op.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let mut tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["a".to_string()]),
+    ///     Tree::Leaf(vec!["b".to_string()])
+    /// ]);
+    /// tree.swap_children(&[], 0, 1).unwrap();
+    /// assert_eq!(tree.children().unwrap()[0], Tree::Leaf(vec!["b".to_string()]));
+    /// ```
+    pub fn swap_children(
+        &mut self,
+        parent_path: &[usize],
+        i: usize,
+        j: usize,
+    ) -> Result<(), PathError> {
+        let parent = self
+            .get_by_path_mut(parent_path)
+            .ok_or_else(|| PathError::NotFound(parent_path.to_vec()))?;
+        let Tree::Node(_, children) = parent else {
+            return Err(PathError::NotANode(parent_path.to_vec()));
+        };
+        let len = children.len();
+        if let Some(index) = [i, j].into_iter().find(|&index| index >= len) {
+            return Err(PathError::IndexOutOfBounds {
+                path: parent_path.to_vec(),
+                index,
+                len,
+            });
+        }
+        children.swap(i, j);
+        Ok(())
+    }
+
+    /// Splits the tree into its top levels and the subtrees cut off below them.
+    ///
+    /// The first element is the tree truncated at `depth` (the root is at depth
+    /// 0): nodes at that depth keep their label but lose their children. The
+    /// second maps the path of each such node to its original subtree, so
+    /// replacing every cut node with its entry restores the original tree.
+    /// Childless nodes and leaves are never cut.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Node("src".to_string(), vec![Tree::Leaf(vec!["main.rs".to_string()])]),
+    ///     Tree::Leaf(vec!["README".to_string()])
+    /// ]);
+    /// let (top, cut) = tree.split_at_depth(1);
+    /// assert_eq!(top, Tree::Node("root".to_string(), vec![
+    ///     Tree::Node("src".to_string(), vec![]),
+    ///     Tree::Leaf(vec!["README".to_string()])
+    /// ]));
+    /// assert_eq!(cut[&vec![0]], tree.children().unwrap()[0]);
+    /// ```
+    pub fn split_at_depth(&self, depth: usize) -> (Tree, HashMap<TreePath, Tree>) {
+        let mut cut = HashMap::new();
+        let top = self.split_at_depth_recursive(depth, &mut Vec::new(), &mut cut);
+        (top, cut)
+    }
+
+    fn split_at_depth_recursive(
+        &self,
+        depth: usize,
+        path: &mut TreePath,
+        cut: &mut HashMap<TreePath, Tree>,
+    ) -> Tree {
+        match self {
+            Tree::Node(label, children) if path.len() == depth && !children.is_empty() => {
+                cut.insert(path.clone(), self.clone());
+                Tree::Node(label.clone(), Vec::new())
+            }
+            Tree::Node(label, children) => {
+                let mut top_children = Vec::with_capacity(children.len());
+                for (index, child) in children.iter().enumerate() {
+                    path.push(index);
+                    top_children.push(child.split_at_depth_recursive(depth, path, cut));
+                    path.pop();
+                }
+                Tree::Node(label.clone(), top_children)
+            }
+            Tree::Leaf(_) => self.clone(),
+        }
+    }
+
+    /// Returns a copy of the tree with the subtrees at all given paths removed.
+    ///
+    /// Paths refer to positions in the original tree, so removing several siblings
+    /// does not require adjusting for index shifts. Paths nested inside another
+    /// removed path are covered by the outer removal, and paths that don't exist
+    /// are ignored. The root cannot be removed, so the empty path is ignored too.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use treelog::Tree;
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["a".to_string()]),
+    ///     Tree::Leaf(vec!["b".to_string()]),
+    ///     Tree::Leaf(vec!["c".to_string()])
+    /// ]);
+    /// let paths = HashSet::from([vec![0], vec![2]]);
+    /// let pruned = tree.remove_paths(&paths);
+    /// assert_eq!(pruned, Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["b".to_string()])
+    /// ]));
+    /// ```
+    pub fn remove_paths(&self, paths: &HashSet<TreePath>) -> Tree {
+        self.remove_paths_recursive(paths, &mut Vec::new())
+    }
+
+    fn remove_paths_recursive(&self, paths: &HashSet<TreePath>, path: &mut TreePath) -> Tree {
+        match self {
+            Tree::Node(label, children) => {
+                let mut kept = Vec::with_capacity(children.len());
+                for (index, child) in children.iter().enumerate() {
+                    path.push(index);
+                    if !paths.contains(path.as_slice()) {
+                        kept.push(child.remove_paths_recursive(paths, path));
+                    }
+                    path.pop();
+                }
+                Tree::Node(label.clone(), kept)
+            }
+            Tree::Leaf(_) => self.clone(),
+        }
+    }
+
     /// Flattens the tree into a list of entries with their paths.
     ///
     /// Returns a vector of `FlattenedEntry` containing the path and content
@@ -134,21 +383,29 @@ impl Tree {
     /// ```
     pub fn flatten(&self) -> Vec<FlattenedEntry> {
         let mut result = Vec::new();
-        self.flatten_recursive(&mut result, &mut Vec::new());
+        self.flatten_recursive(&mut result, &mut Vec::new(), None);
         result
     }
 
-    fn flatten_recursive(&self, result: &mut Vec<FlattenedEntry>, path: &mut TreePath) {
+    fn flatten_recursive(
+        &self,
+        result: &mut Vec<FlattenedEntry>,
+        path: &mut TreePath,
+        parent: Option<usize>,
+    ) {
         match self {
             Tree::Node(label, children) => {
+                let index = result.len();
                 result.push(FlattenedEntry {
                     path: path.clone(),
                     content: label.clone(),
                     is_node: true,
+                    parent,
+                    lines: Vec::new(),
                 });
-                for (index, child) in children.iter().enumerate() {
-                    path.push(index);
-                    child.flatten_recursive(result, path);
+                for (child_index, child) in children.iter().enumerate() {
+                    path.push(child_index);
+                    child.flatten_recursive(result, path, Some(index));
                     path.pop();
                 }
             }
@@ -158,10 +415,134 @@ impl Tree {
                     path: path.clone(),
                     content,
                     is_node: false,
+                    parent,
+                    lines: lines.clone(),
                 });
             }
         }
     }
+
+    /// Flattens the tree into a [`FlatTree`] of pre-order records.
+    ///
+    /// This is the same list as [`flatten`](Self::flatten), wrapped for
+    /// serialization and rebuilding with [`from_flat`](Self::from_flat).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["item".to_string()])
+    /// ]);
+    /// let flat = tree.to_flat();
+    /// assert_eq!(flat.entries.len(), 2);
+    /// ```
+    pub fn to_flat(&self) -> FlatTree {
+        FlatTree {
+            entries: self.flatten(),
+        }
+    }
+
+    /// Rebuilds a tree from a [`FlatTree`].
+    ///
+    /// The tree shape comes from the parent indices: the first entry is the root,
+    /// and children keep the order in which they appear. The `path` of each entry
+    /// is not consulted.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FlatTreeError`] if there are no entries, an entry after the
+    /// first has no parent, or a parent index doesn't refer to an earlier node.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["a".to_string(), "b".to_string()])
+    /// ]);
+    /// assert_eq!(Tree::from_flat(&tree.to_flat()).unwrap(), tree);
+    /// ```
+    pub fn from_flat(flat: &FlatTree) -> Result<Tree, FlatTreeError> {
+        let entries = &flat.entries;
+        if entries.is_empty() {
+            return Err(FlatTreeError::Empty);
+        }
+        let mut children: Vec<Vec<usize>> = vec![Vec::new(); entries.len()];
+        for (index, entry) in entries.iter().enumerate().skip(1) {
+            let parent = entry.parent.ok_or(FlatTreeError::MultipleRoots(index))?;
+            if parent >= index || !entries[parent].is_node {
+                return Err(FlatTreeError::InvalidParent { index, parent });
+            }
+            children[parent].push(index);
+        }
+        Ok(Self::from_flat_entry(entries, &children, 0))
+    }
+
+    fn from_flat_entry(entries: &[FlattenedEntry], children: &[Vec<usize>], index: usize) -> Tree {
+        let entry = &entries[index];
+        if entry.is_node {
+            Tree::Node(
+                entry.content.clone(),
+                children[index]
+                    .iter()
+                    .map(|&child| Self::from_flat_entry(entries, children, child))
+                    .collect(),
+            )
+        } else {
+            Tree::Leaf(entry.lines.clone())
+        }
+    }
+
+    /// Returns every root-to-leaf path as a sequence of labels.
+    ///
+    /// Each path contains the labels of the nodes from the root down to a leaf,
+    /// followed by the leaf's first line. Paths are returned in pre-order.
+    /// Nodes without leaves below them produce no paths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Node("dir".to_string(), vec![
+    ///         Tree::Leaf(vec!["file".to_string()])
+    ///     ]),
+    ///     Tree::Leaf(vec!["other".to_string()])
+    /// ]);
+    /// let paths = tree.root_to_leaf_paths();
+    /// assert_eq!(paths[0], vec!["root", "dir", "file"]);
+    /// assert_eq!(paths[1], vec!["root", "other"]);
+    /// ```
+    pub fn root_to_leaf_paths(&self) -> Vec<Vec<String>> {
+        let mut result = Vec::new();
+        self.root_to_leaf_paths_recursive(&mut result, &mut Vec::new());
+        result
+    }
+
+    fn root_to_leaf_paths_recursive(
+        &self,
+        result: &mut Vec<Vec<String>>,
+        labels: &mut Vec<String>,
+    ) {
+        match self {
+            Tree::Node(label, children) => {
+                labels.push(label.clone());
+                for child in children {
+                    child.root_to_leaf_paths_recursive(result, labels);
+                }
+                labels.pop();
+            }
+            Tree::Leaf(lines) => {
+                let mut path = labels.clone();
+                path.push(lines.first().cloned().unwrap_or_default());
+                result.push(path);
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -238,4 +619,230 @@ mod tests {
         assert_eq!(flattened[2].content, "item");
         assert!(!flattened[2].is_node);
     }
+
+    #[test]
+    fn test_flat_round_trip() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node(
+                    "child".to_string(),
+                    vec![
+                        Tree::Leaf(vec!["item".to_string(), "more".to_string()]),
+                        Tree::Node("empty".to_string(), vec![]),
+                    ],
+                ),
+                Tree::Leaf(vec![]),
+                Tree::Leaf(vec!["leaf2".to_string()]),
+            ],
+        );
+        let flat = tree.to_flat();
+        let parents: Vec<Option<usize>> = flat.entries.iter().map(|e| e.parent).collect();
+        assert_eq!(
+            parents,
+            vec![None, Some(0), Some(1), Some(1), Some(0), Some(0)]
+        );
+        assert_eq!(Tree::from_flat(&flat), Ok(tree));
+
+        let leaf = Tree::Leaf(vec!["only".to_string()]);
+        assert_eq!(Tree::from_flat(&leaf.to_flat()), Ok(leaf));
+    }
+
+    #[test]
+    fn test_from_flat_errors() {
+        assert_eq!(
+            Tree::from_flat(&FlatTree::default()),
+            Err(FlatTreeError::Empty)
+        );
+
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Leaf(vec!["a".to_string()]),
+                Tree::Leaf(vec!["b".to_string()]),
+            ],
+        );
+        let mut flat = tree.to_flat();
+        flat.entries[2].parent = None;
+        assert_eq!(Tree::from_flat(&flat), Err(FlatTreeError::MultipleRoots(2)));
+
+        flat.entries[2].parent = Some(1);
+        assert_eq!(
+            Tree::from_flat(&flat),
+            Err(FlatTreeError::InvalidParent {
+                index: 2,
+                parent: 1
+            })
+        );
+    }
+
+    #[cfg(feature = "serde-json")]
+    #[test]
+    fn test_flat_tree_serde() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![Tree::Leaf(vec!["item".to_string()])],
+        );
+        let flat = tree.to_flat();
+        let json = serde_json::to_value(&flat).unwrap();
+        assert!(json.is_array());
+        assert_eq!(json[1]["parent"], 0);
+        assert_eq!(json[1]["lines"][0], "item");
+
+        let decoded: FlatTree = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, flat);
+        assert_eq!(Tree::from_flat(&decoded), Ok(tree));
+    }
+
+    #[test]
+    fn test_split_at_depth() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node(
+                    "a".to_string(),
+                    vec![
+                        Tree::Node("a1".to_string(), vec![Tree::Leaf(vec!["x".to_string()])]),
+                        Tree::Leaf(vec!["a2".to_string()]),
+                        Tree::Node("empty".to_string(), vec![]),
+                    ],
+                ),
+                Tree::Leaf(vec!["b".to_string()]),
+                Tree::Node("c".to_string(), vec![Tree::Leaf(vec!["c1".to_string()])]),
+            ],
+        );
+
+        let (top, cut) = tree.split_at_depth(2);
+        let mut keys: Vec<&TreePath> = cut.keys().collect();
+        keys.sort();
+        assert_eq!(keys, vec![&vec![0, 0]]);
+        assert_eq!(
+            top.get_by_path(&[0, 0]),
+            Some(&Tree::Node("a1".to_string(), vec![]))
+        );
+        assert_eq!(top.get_by_path(&[2, 0]), tree.get_by_path(&[2, 0]));
+
+        for depth in 0..4 {
+            let (mut top, cut) = tree.split_at_depth(depth);
+            for (path, subtree) in cut {
+                *top.get_by_path_mut(&path).unwrap() = subtree;
+            }
+            assert_eq!(top, tree, "depth {depth}");
+        }
+
+        let (top, cut) = tree.split_at_depth(0);
+        assert_eq!(top, Tree::Node("root".to_string(), vec![]));
+        assert_eq!(cut[&vec![]], tree);
+    }
+
+    #[test]
+    fn test_root_to_leaf_paths() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node(
+                    "a".to_string(),
+                    vec![
+                        Tree::Leaf(vec!["a1".to_string(), "more".to_string()]),
+                        Tree::Leaf(vec!["a2".to_string()]),
+                    ],
+                ),
+                Tree::Node("empty".to_string(), vec![]),
+                Tree::Leaf(vec!["b".to_string()]),
+            ],
+        );
+        assert_eq!(
+            tree.root_to_leaf_paths(),
+            vec![
+                vec!["root", "a", "a1"],
+                vec!["root", "a", "a2"],
+                vec!["root", "b"],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_swap_children() {
+        let mut tree = Tree::Node(
+            "root".to_string(),
+            vec![Tree::Node(
+                "dir".to_string(),
+                vec![
+                    Tree::Leaf(vec!["a".to_string()]),
+                    Tree::Node("b".to_string(), vec![Tree::Leaf(vec!["b1".to_string()])]),
+                    Tree::Leaf(vec!["c".to_string()]),
+                ],
+            )],
+        );
+        let original = tree.clone();
+
+        tree.swap_children(&[0], 0, 2).unwrap();
+        let labels: Vec<_> = tree.children().unwrap()[0]
+            .children()
+            .unwrap()
+            .iter()
+            .map(|child| child.label().unwrap_or_else(|| &child.lines().unwrap()[0]))
+            .collect();
+        assert_eq!(labels, vec!["c", "b", "a"]);
+        assert_eq!(tree.get_by_path(&[0, 1]), original.get_by_path(&[0, 1]));
+
+        tree.swap_children(&[0], 2, 0).unwrap();
+        assert_eq!(tree, original);
+
+        assert_eq!(
+            tree.swap_children(&[0], 0, 3),
+            Err(PathError::IndexOutOfBounds {
+                path: vec![0],
+                index: 3,
+                len: 3
+            })
+        );
+        assert_eq!(
+            tree.swap_children(&[0, 0], 0, 1),
+            Err(PathError::NotANode(vec![0, 0]))
+        );
+        assert_eq!(
+            tree.swap_children(&[5], 0, 1),
+            Err(PathError::NotFound(vec![5]))
+        );
+        assert_eq!(tree, original);
+    }
+
+    #[test]
+    fn test_remove_paths() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node(
+                    "dir".to_string(),
+                    vec![
+                        Tree::Leaf(vec!["a".to_string()]),
+                        Tree::Leaf(vec!["b".to_string()]),
+                        Tree::Leaf(vec!["c".to_string()]),
+                        Tree::Leaf(vec!["d".to_string()]),
+                    ],
+                ),
+                Tree::Node("other".to_string(), vec![Tree::Leaf(vec!["x".to_string()])]),
+            ],
+        );
+
+        let paths = HashSet::from([vec![0, 1], vec![0, 2]]);
+        let pruned = tree.remove_paths(&paths);
+        assert_eq!(
+            pruned.get_by_path(&[0]),
+            Some(&Tree::Node(
+                "dir".to_string(),
+                vec![
+                    Tree::Leaf(vec!["a".to_string()]),
+                    Tree::Leaf(vec!["d".to_string()]),
+                ],
+            ))
+        );
+        assert_eq!(pruned.get_by_path(&[1]), tree.get_by_path(&[1]));
+
+        let overlapping = HashSet::from([vec![1], vec![1, 0], vec![], vec![7]]);
+        let pruned = tree.remove_paths(&overlapping);
+        assert_eq!(pruned.child_count(), Some(1));
+        assert_eq!(pruned.get_by_path(&[0]), tree.get_by_path(&[0]));
+    }
 }
@@ -1,12 +1,14 @@
 //! Core rendering logic for trees.
 
+use std::collections::HashMap;
 use std::fmt;
 use std::fmt::Write;
 
-use crate::config::RenderConfig;
+use crate::config::{CountMode, RenderConfig};
 use crate::level::LevelPath;
+use crate::prefix::compute_prefix;
 use crate::tree::Tree;
-use crate::utils::estimate_capacity;
+use crate::utils::{display_width, estimate_capacity, expand_tabs};
 
 /// Renders a tree to a writer using the default configuration.
 ///
@@ -42,17 +44,77 @@ pub fn write_tree_with_config(
     tree: &Tree,
     config: &RenderConfig,
 ) -> fmt::Result {
-    write_tree_element(f, tree, &LevelPath::new(), config)
+    RenderContext::new(tree, config).write(f, tree)
+}
+
+/// Function styling the text of an element's lines given its path, in place of the usual colors.
+type RestyleFn<'a> = dyn Fn(&[usize], String) -> String + 'a;
+
+/// Settings that stay the same for every element of one render.
+struct RenderContext<'a> {
+    config: &'a RenderConfig,
+    /// Column at which all labels start, when labels are aligned
+    label_column: Option<usize>,
+    /// Text appended to the first line of the element at each path
+    annotations: Option<&'a HashMap<Vec<usize>, String>>,
+    /// Styling applied to each line instead of the configured colors
+    restyle: Option<&'a RestyleFn<'a>>,
+}
+
+impl<'a> RenderContext<'a> {
+    fn new(tree: &Tree, config: &'a RenderConfig) -> Self {
+        let label_column = config.align_labels.then(|| {
+            max_render_depth(tree, &mut Vec::new(), config) * config.style.branch.chars().count()
+        });
+        RenderContext {
+            config,
+            label_column,
+            annotations: None,
+            restyle: None,
+        }
+    }
+
+    fn write(&self, f: &mut dyn Write, tree: &Tree) -> fmt::Result {
+        write_tree_element(f, tree, &LevelPath::new(), &mut Vec::new(), None, self)
+    }
+
+    /// Returns whether an annotation or glyph override targets any of the children
+    /// of the node at `path`, which would be lost if the children were joined.
+    fn targets_any_child(&self, path: &mut Vec<usize>, children: &[Tree]) -> bool {
+        children.iter().enumerate().any(|(index, child)| {
+            path.push(index);
+            let targeted = self
+                .annotations
+                .is_some_and(|annotations| annotations.contains_key(path.as_slice()))
+                || self.config.glyph_override(path, child).is_some();
+            path.pop();
+            targeted
+        })
+    }
+
+    /// Returns the annotation of the element at `path`, preceded by the gutter.
+    fn annotation(&self, path: &[usize]) -> Option<String> {
+        let annotation = self.annotations?.get(path)?;
+        Some(format!(
+            "{}{annotation}",
+            " ".repeat(self.config.annotation_gutter)
+        ))
+    }
 }
 
 fn write_tree_element(
     f: &mut dyn Write,
     tree: &Tree,
     level: &LevelPath,
-    config: &RenderConfig,
+    path: &mut Vec<usize>,
+    key_width: Option<usize>,
+    context: &RenderContext,
 ) -> fmt::Result {
+    let config = context.config;
+    let label_column = context.label_column;
     let style = &config.style;
     let maxpos = level.len();
+    let mut prefix = String::new();
     let mut second_line = String::new();
 
     // Build the prefix for the current line
@@ -61,26 +123,72 @@ fn write_tree_element(
         if is_last {
             // This branch is the last child at this level
             if !last_row {
-                write!(f, "{}", style.get_empty())?;
+                prefix.push_str(style.get_empty());
             } else {
-                write!(f, "{}", style.get_branch(true))?;
+                prefix.push_str(style.get_branch(true));
             }
             second_line.push_str(style.get_empty());
         } else {
             // This branch is not the last child
             if !last_row {
-                write!(f, "{}", style.get_vertical())?;
+                prefix.push_str(style.get_vertical());
             } else {
-                write!(f, "{}", style.get_branch(false))?;
+                prefix.push_str(style.get_branch(false));
             }
             second_line.push_str(style.get_vertical());
         }
     }
 
+    if maxpos > 0
+        && let Some(glyph) = config.glyph_override(path, tree)
+    {
+        let is_last = level.iter().last().unwrap_or(false);
+        prefix.truncate(prefix.len() - style.get_branch(is_last).len());
+        prefix.push_str(&glyph);
+    }
+
+    if let Some(column) = label_column {
+        let fill = if maxpos > 0 {
+            style.branch.trim_end().chars().last().unwrap_or(' ')
+        } else {
+            ' '
+        };
+        prefix = pad_prefix(&prefix, column, fill);
+        second_line = pad_prefix(&second_line, column, ' ');
+    }
+
     match tree {
         Tree::Node(label, children) => {
-            let formatted_label = config.format_node(label);
-            let final_label = if config.colors {
+            let collapsed = !children.is_empty() && config.collapsed.contains(path.as_slice());
+            let as_leaf = children.is_empty() && config.empty_nodes_as_leaves;
+            let expanded;
+            let text = if config.expand_tabs {
+                expanded = expand_tabs(label, config.tab_width, prefix.chars().count());
+                &expanded
+            } else {
+                label
+            };
+            let mut formatted_label = if as_leaf {
+                config.format_leaf(text)
+            } else {
+                config.format_node(text)
+            };
+            if config.show_child_counts && !as_leaf {
+                let count = match config.count_mode {
+                    CountMode::Children => children.len(),
+                    CountMode::Descendants => descendant_count(children),
+                };
+                formatted_label.push_str(&format!(" ({count})"));
+            }
+            if collapsed && let Some(count) = config.collapsed_preview {
+                formatted_label.push(' ');
+                formatted_label.push_str(&collapsed_preview(children, count));
+            }
+            let mut final_label = if let Some(restyle) = context.restyle {
+                restyle(path, formatted_label)
+            } else if as_leaf {
+                color_leaf(config, label, formatted_label)
+            } else if config.colors {
                 #[cfg(feature = "color")]
                 {
                     use colored::Colorize;
@@ -93,42 +201,92 @@ fn write_tree_element(
             } else {
                 formatted_label
             };
-            write!(f, "{}{}", final_label, config.line_ending)?;
+            if let Some(annotation) = context.annotation(path) {
+                final_label.push_str(&annotation);
+            }
+            write_line(f, &prefix, &final_label, &config.line_ending, config)?;
+
+            if collapsed {
+                if config.collapsed_preview.is_none() {
+                    let mut summary_prefix = compute_prefix(&level.with_child(true), style);
+                    if let Some(column) = label_column {
+                        summary_prefix = pad_prefix(&summary_prefix, column, ' ');
+                    }
+                    let summary = config.format_summary(children.len());
+                    write_line(f, &summary_prefix, &summary, &config.line_ending, config)?;
+                }
+                return Ok(());
+            }
+
+            if let Some(separator) = &config.join_leaf_siblings
+                && !context.targets_any_child(path, children)
+                && let Some(joined) = joined_leaf_siblings(children, separator)
+            {
+                path.push(0);
+                write_tree_element(f, &joined, &level.with_child(true), path, None, context)?;
+                path.pop();
+                return Ok(());
+            }
 
+            let key_width = config.align_on.and_then(|delimiter| {
+                let start = compute_prefix(&level.with_child(false), style)
+                    .chars()
+                    .count()
+                    .max(label_column.unwrap_or(0));
+                aligned_key_width(children, delimiter, config.tab_width, start)
+            });
             let mut remaining = children.len();
-            for child in children {
+            for (index, child) in children.iter().enumerate() {
                 let is_last = remaining == 1;
                 let lnext = level.with_child(is_last);
                 remaining -= 1;
-                write_tree_element(f, child, &lnext, config)?;
+                path.push(index);
+                write_tree_element(f, child, &lnext, path, key_width, context)?;
+                path.pop();
+                if path.is_empty() && config.blank_line_between_top_level && !is_last {
+                    f.write_str(&config.line_ending)?;
+                }
             }
         }
         Tree::Leaf(lines) => {
+            if let Some(bullet) = &config.leaf_bullet {
+                prefix.push_str(bullet);
+                second_line.push_str(&" ".repeat(bullet.chars().count()));
+            }
+            let line_ending = format!("{}\n", config.line_ending.trim_end());
             for (i, line) in lines.iter().enumerate() {
-                let formatted_line = config.format_leaf(line);
-                let final_line = if config.colors {
-                    #[cfg(feature = "color")]
-                    {
-                        use colored::Colorize;
-                        formatted_line.green().to_string()
-                    }
-                    #[cfg(not(feature = "color"))]
-                    {
-                        formatted_line
-                    }
+                let start = if i == 0 { &prefix } else { &second_line }.chars().count();
+                let expanded;
+                let text = if config.expand_tabs {
+                    expanded = expand_tabs(line, config.tab_width, start);
+                    &expanded
                 } else {
-                    formatted_line
+                    line
                 };
+                let formatted_line = match (config.align_on, key_width) {
+                    (Some(delimiter), Some(width)) => config.format_leaf(&align_line(
+                        text,
+                        delimiter,
+                        width,
+                        config.tab_width,
+                        start,
+                    )),
+                    _ => config.format_leaf(text),
+                };
+                let final_line = if let Some(restyle) = context.restyle {
+                    restyle(path, formatted_line)
+                } else {
+                    color_leaf(config, line, formatted_line)
+                };
+                let mut final_line = config.hyperlink(line, final_line);
                 if i == 0 {
-                    writeln!(f, "{}{}", final_line, config.line_ending.trim_end())?;
+                    if let Some(annotation) = context.annotation(path) {
+                        final_line.push_str(&annotation);
+                    }
+                    write_line(f, &prefix, &final_line, &line_ending, config)?;
                 } else {
-                    writeln!(
-                        f,
-                        "{} {}{}",
-                        second_line,
-                        final_line,
-                        config.line_ending.trim_end()
-                    )?;
+                    // Continuation lines align under the first line's content
+                    write_line(f, &second_line, &final_line, &line_ending, config)?;
                 }
             }
         }
@@ -137,6 +295,155 @@ fn write_tree_element(
     Ok(())
 }
 
+/// Applies the configured leaf color for `line` to its formatted text, if colors are enabled.
+fn color_leaf(config: &RenderConfig, line: &str, formatted_line: String) -> String {
+    if !config.colors {
+        return formatted_line;
+    }
+    #[cfg(feature = "color")]
+    {
+        use crate::config::Severity;
+        use colored::Colorize;
+        match config.classify(line) {
+            Severity::Error => formatted_line.red().to_string(),
+            Severity::Warning => formatted_line.yellow().to_string(),
+            Severity::Info => formatted_line.cyan().to_string(),
+            Severity::Normal => formatted_line.green().to_string(),
+        }
+    }
+    #[cfg(not(feature = "color"))]
+    {
+        let _ = line;
+        formatted_line
+    }
+}
+
+/// Joins children that are all single-line leaves into one leaf, or returns `None`.
+fn joined_leaf_siblings(children: &[Tree], separator: &str) -> Option<Tree> {
+    let lines = children
+        .iter()
+        .map(|child| match child {
+            Tree::Leaf(lines) if lines.len() == 1 => Some(lines[0].as_str()),
+            _ => None,
+        })
+        .collect::<Option<Vec<&str>>>()?;
+    (!lines.is_empty()).then(|| Tree::Leaf(vec![lines.join(separator)]))
+}
+
+/// Counts all nodes and leaves below a node with the given children.
+fn descendant_count(children: &[Tree]) -> usize {
+    children
+        .iter()
+        .map(|child| match child {
+            Tree::Node(_, grandchildren) => 1 + descendant_count(grandchildren),
+            Tree::Leaf(_) => 1,
+        })
+        .sum()
+}
+
+/// Returns the deepest level at which a line is rendered, honoring collapsed nodes.
+fn max_render_depth(tree: &Tree, path: &mut Vec<usize>, config: &RenderConfig) -> usize {
+    match tree {
+        Tree::Node(_, children) if children.is_empty() => path.len(),
+        Tree::Node(_, _) if config.collapsed.contains(path.as_slice()) => {
+            path.len() + usize::from(config.collapsed_preview.is_none())
+        }
+        Tree::Node(_, children) => {
+            let mut depth = path.len();
+            for (index, child) in children.iter().enumerate() {
+                path.push(index);
+                depth = depth.max(max_render_depth(child, path, config));
+                path.pop();
+            }
+            depth
+        }
+        Tree::Leaf(_) => path.len(),
+    }
+}
+
+/// Pads `prefix` to `column` characters, inserting `fill` before its trailing spaces.
+fn pad_prefix(prefix: &str, column: usize, fill: char) -> String {
+    let width = prefix.chars().count();
+    if width >= column {
+        return prefix.to_string();
+    }
+    let glyphs = prefix.trim_end_matches(' ');
+    let spaces = &prefix[glyphs.len()..];
+    let padding: String = std::iter::repeat_n(fill, column - width).collect();
+    format!("{glyphs}{padding}{spaces}")
+}
+
+/// Builds the inline `[a, b, …]` preview of a collapsed node's first children.
+fn collapsed_preview(children: &[Tree], count: usize) -> String {
+    let labels: Vec<&str> = children
+        .iter()
+        .take(count)
+        .map(|child| match child {
+            Tree::Node(label, _) => label.as_str(),
+            Tree::Leaf(lines) => lines.first().map(|s| s.as_str()).unwrap_or(""),
+        })
+        .collect();
+    let mut preview = format!("[{}", labels.join(", "));
+    if children.len() > count {
+        if !labels.is_empty() {
+            preview.push_str(", ");
+        }
+        preview.push('…');
+    }
+    preview.push(']');
+    preview
+}
+
+/// Returns the widest key (text before `delimiter`) among the lines of the leaf children.
+///
+/// Widths are measured in columns for lines starting at column `start`, with tabs
+/// expanded to `tab_width`.
+fn aligned_key_width(
+    children: &[Tree],
+    delimiter: char,
+    tab_width: usize,
+    start: usize,
+) -> Option<usize> {
+    children
+        .iter()
+        .filter_map(|child| match child {
+            Tree::Leaf(lines) => Some(lines),
+            Tree::Node(..) => None,
+        })
+        .flatten()
+        .filter_map(|line| line.split_once(delimiter))
+        .map(|(key, _)| display_width(key, tab_width, start))
+        .max()
+}
+
+/// Pads the key of `line` (text before `delimiter`, starting at column `start`) to
+/// `width` columns.
+fn align_line(line: &str, delimiter: char, width: usize, tab_width: usize, start: usize) -> String {
+    match line.split_once(delimiter) {
+        Some((key, value)) => {
+            let padding = width.saturating_sub(display_width(key, tab_width, start));
+            format!("{key}{:padding$}{delimiter}{value}", "")
+        }
+        None => line.to_string(),
+    }
+}
+
+/// Writes a single physical line, applying line-level options such as trimming.
+fn write_line(
+    f: &mut dyn Write,
+    prefix: &str,
+    content: &str,
+    line_ending: &str,
+    config: &RenderConfig,
+) -> fmt::Result {
+    if config.trim_trailing {
+        let line = format!("{prefix}{content}");
+        write!(f, "{}{}", line.trim_end(), line_ending)
+    } else {
+        write!(f, "{prefix}{content}{line_ending}")
+    }
+}
+
 /// Renders a tree to a String using the default configuration.
 ///
 /// # Examples
@@ -171,6 +478,129 @@ pub fn render_to_string_with_config(tree: &Tree, config: &RenderConfig) -> Strin
     output
 }
 
+/// Wrapper whose `Debug` output is the rendered tree, created by [`Tree::debug_tree`].
+///
+/// Useful with `dbg!` and `{:?}` in assertions, where the derived `Debug` of
+/// [`Tree`] prints deeply nested `Node(...)` and `Leaf(...)` values.
+///
+/// # Examples
+///
+/// ```
+/// use treelog::Tree;
+///
+/// let tree = Tree::Node("root".to_string(), vec![Tree::Leaf(vec!["item".to_string()])]);
+/// assert_eq!(format!("{:?}", tree.debug_tree()), "root\n└─ item\n");
+/// ```
+#[derive(Clone, Copy)]
+pub struct DebugTree<'a>(pub(crate) &'a Tree);
+
+impl fmt::Debug for DebugTree<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_tree(f, self.0)
+    }
+}
+
+/// Renders several independent trees one after another, each with its root at column 0.
+///
+/// Each tree is rendered as by [`render_to_string_with_config`]. When
+/// [`blank_line_between_top_level`](RenderConfig::blank_line_between_top_level)
+/// is enabled, a blank line separates consecutive trees as well.
+///
+/// # Examples
+///
+/// ```
+/// use treelog::{RenderConfig, Tree};
+/// use treelog::renderer::render_forest;
+///
+/// let trees = vec![
+///     Tree::Node("a".to_string(), vec![Tree::Leaf(vec!["x".to_string()])]),
+///     Tree::Node("b".to_string(), vec![Tree::Leaf(vec!["y".to_string()])]),
+/// ];
+/// let output = render_forest(&trees, &RenderConfig::default());
+/// assert_eq!(output, "a\n└─ x\nb\n└─ y\n");
+/// ```
+pub fn render_forest(trees: &[Tree], config: &RenderConfig) -> String {
+    let capacity = trees.iter().map(|tree| estimate_capacity(tree, 20)).sum();
+    let mut output = String::with_capacity(capacity);
+    for (index, tree) in trees.iter().enumerate() {
+        if index > 0 && config.blank_line_between_top_level {
+            output.push_str(&config.line_ending);
+        }
+        write_tree_with_config(&mut output, tree, config).unwrap();
+    }
+    output
+}
+
+/// Renders a tree to a String, appending annotations to the elements at the given paths.
+pub(crate) fn render_to_string_with_annotations(
+    tree: &Tree,
+    annotations: &HashMap<Vec<usize>, String>,
+    config: &RenderConfig,
+) -> String {
+    let capacity = estimate_capacity(tree, 20);
+    let mut output = String::with_capacity(capacity);
+    let mut context = RenderContext::new(tree, config);
+    context.annotations = Some(annotations);
+    context.write(&mut output, tree).unwrap();
+    output
+}
+
+/// Renders at most `max_lines` lines of a tree, also returning whether output was cut off.
+///
+/// Rendering stops at the first write past the limit, so the rest of the tree is never rendered.
+pub(crate) fn render_to_string_limited(
+    tree: &Tree,
+    config: &RenderConfig,
+    max_lines: usize,
+) -> (String, bool) {
+    let mut output = LineLimit {
+        output: String::new(),
+        lines_left: max_lines,
+        truncated: false,
+    };
+    // An error here only means the limit was reached
+    let _ = RenderContext::new(tree, config).write(&mut output, tree);
+    (output.output, output.truncated)
+}
+
+/// Writer keeping the first lines written to it and failing once more output follows.
+struct LineLimit {
+    output: String,
+    lines_left: usize,
+    truncated: bool,
+}
+
+impl Write for LineLimit {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for piece in s.split_inclusive('\n') {
+            if self.lines_left == 0 {
+                self.truncated = true;
+                return Err(fmt::Error);
+            }
+            self.output.push_str(piece);
+            if piece.ends_with('\n') {
+                self.lines_left -= 1;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Renders a tree to a String, styling each line with `restyle` instead of the configured colors.
+#[cfg(all(feature = "compare", feature = "color"))]
+pub(crate) fn render_to_string_restyled(
+    tree: &Tree,
+    restyle: &RestyleFn<'_>,
+    config: &RenderConfig,
+) -> String {
+    let capacity = estimate_capacity(tree, 20);
+    let mut output = String::with_capacity(capacity);
+    let mut context = RenderContext::new(tree, config);
+    context.restyle = Some(restyle);
+    context.write(&mut output, tree).unwrap();
+    output
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -212,6 +642,67 @@ mod tests {
         assert!(output.contains("item"));
     }
 
+    #[test]
+    fn test_trim_trailing() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node("".to_string(), vec![Tree::Leaf(vec!["".to_string()])]),
+                Tree::Leaf(vec!["item  ".to_string(), "".to_string()]),
+            ],
+        );
+        let untrimmed = render_to_string(&tree);
+        assert!(untrimmed.lines().any(|line| line.ends_with(' ')));
+
+        let config = RenderConfig::default().with_trim_trailing(true);
+        let output = render_to_string_with_config(&tree, &config);
+        assert!(output.lines().all(|line| !line.ends_with(' ')));
+        assert_eq!(output.lines().count(), untrimmed.lines().count());
+        for (trimmed, original) in output.lines().zip(untrimmed.lines()) {
+            assert_eq!(trimmed, original.trim_end());
+        }
+    }
+
+    #[test]
+    fn test_collapsed() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node(
+                    "dir".to_string(),
+                    vec![
+                        Tree::Leaf(vec!["a".to_string()]),
+                        Tree::Leaf(vec!["b".to_string()]),
+                        Tree::Node("c".to_string(), vec![]),
+                        Tree::Leaf(vec!["d".to_string()]),
+                        Tree::Leaf(vec!["e".to_string()]),
+                    ],
+                ),
+                Tree::Leaf(vec!["other".to_string()]),
+            ],
+        );
+
+        let config = RenderConfig::default().with_collapsed(vec![0]);
+        let output = render_to_string_with_config(&tree, &config);
+        assert_eq!(output, "root\n├─ dir\n│  └─ … (5 more)\n└─ other\n");
+
+        let config = config.with_collapsed_preview(2);
+        let output = render_to_string_with_config(&tree, &config);
+        assert_eq!(output, "root\n├─ dir [a, b, …]\n└─ other\n");
+
+        let config = RenderConfig::default()
+            .with_collapsed(vec![0])
+            .with_summary_fn(|n| format!("<{n} hidden>"));
+        let output = render_to_string_with_config(&tree, &config);
+        assert!(output.contains("│  └─ <5 hidden>\n"));
+
+        let config = RenderConfig::default()
+            .with_collapsed(vec![0])
+            .with_collapsed_preview(5);
+        let output = render_to_string_with_config(&tree, &config);
+        assert!(output.contains("├─ dir [a, b, c, d, e]\n"));
+    }
+
     #[test]
     fn test_complex_tree() {
         let l1 = Tree::Leaf(vec![
@@ -234,4 +725,426 @@ mod tests {
         assert!(output.contains("node 2"));
         assert!(output.contains("node 3"));
     }
+
+    #[test]
+    fn test_align_on() {
+        let tree = Tree::Node(
+            "package".to_string(),
+            vec![
+                Tree::Leaf(vec!["name: x".to_string()]),
+                Tree::Leaf(vec!["version: 1.0".to_string()]),
+                Tree::Leaf(vec!["plain".to_string()]),
+                Tree::Node(
+                    "deps".to_string(),
+                    vec![Tree::Leaf(vec!["a: 1".to_string()])],
+                ),
+            ],
+        );
+        let config = RenderConfig::default().with_align_on(':');
+        let output = render_to_string_with_config(&tree, &config);
+        assert_eq!(
+            output,
+            "package\n├─ name   : x\n├─ version: 1.0\n├─ plain\n└─ deps\n   └─ a: 1\n"
+        );
+    }
+
+    #[test]
+    fn test_hyperlink_fn() {
+        let tree = Tree::Node(
+            "src".to_string(),
+            vec![
+                Tree::Leaf(vec!["main.rs".to_string()]),
+                Tree::Leaf(vec!["README".to_string()]),
+            ],
+        );
+        let config = RenderConfig::default()
+            .with_hyperlink_fn(|line| line.ends_with(".rs").then(|| format!("file:///src/{line}")));
+        let output = render_to_string_with_config(&tree, &config);
+        assert_eq!(
+            output,
+            "src\n├─ \x1b]8;;file:///src/main.rs\x1b\\main.rs\x1b]8;;\x1b\\\n└─ README\n"
+        );
+    }
+
+    #[test]
+    fn test_multi_line_leaf_alignment() {
+        let root_leaf = Tree::Leaf(vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+        assert_eq!(render_to_string(&root_leaf), "a\nb\nc\n");
+
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Leaf(vec!["first".to_string(), "second".to_string()]),
+                Tree::Leaf(vec!["last".to_string(), "more".to_string()]),
+            ],
+        );
+        assert_eq!(
+            render_to_string(&tree),
+            "root\n├─ first\n│  second\n└─ last\n   more\n"
+        );
+    }
+
+    #[test]
+    fn test_multi_line_middle_leaf_guides() {
+        let inner = || {
+            vec![
+                Tree::Leaf(vec!["x1".to_string(), "x2".to_string()]),
+                Tree::Leaf(vec!["y".to_string()]),
+            ]
+        };
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node("a".to_string(), inner()),
+                Tree::Node("b".to_string(), inner()),
+            ],
+        );
+        assert_eq!(
+            render_to_string(&tree),
+            "root\n├─ a\n│  ├─ x1\n│  │  x2\n│  └─ y\n└─ b\n   ├─ x1\n   │  x2\n   └─ y\n"
+        );
+    }
+
+    #[test]
+    fn test_leaf_bullet() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Leaf(vec!["first".to_string(), "second".to_string()]),
+                Tree::Node(
+                    "child".to_string(),
+                    vec![Tree::Leaf(vec!["item".to_string()])],
+                ),
+            ],
+        );
+        let config = RenderConfig::default().with_leaf_bullet("• ");
+        assert_eq!(
+            render_to_string_with_config(&tree, &config),
+            "root\n├─ • first\n│    second\n└─ child\n   └─ • item\n"
+        );
+
+        let config = RenderConfig::default().with_leaf_bullet("- ");
+        let output = render_to_string_with_config(&tree, &config);
+        let column = |line: &str, text: &str| line[..line.find(text).unwrap()].chars().count();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(column(lines[1], "first"), column(lines[2], "second"));
+        assert!(!output.contains("- root") && !output.contains("- child"));
+    }
+
+    #[test]
+    fn test_join_leaf_siblings() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node(
+                    "flat".to_string(),
+                    vec![
+                        Tree::Leaf(vec!["a".to_string()]),
+                        Tree::Leaf(vec!["b".to_string()]),
+                        Tree::Leaf(vec!["c".to_string()]),
+                    ],
+                ),
+                Tree::Node(
+                    "mixed".to_string(),
+                    vec![
+                        Tree::Leaf(vec!["x".to_string()]),
+                        Tree::Node("sub".to_string(), vec![]),
+                    ],
+                ),
+                Tree::Node(
+                    "multi".to_string(),
+                    vec![
+                        Tree::Leaf(vec!["y".to_string()]),
+                        Tree::Leaf(vec!["z1".to_string(), "z2".to_string()]),
+                    ],
+                ),
+            ],
+        );
+        let config = RenderConfig::default().with_join_leaf_siblings(", ");
+        assert_eq!(
+            render_to_string_with_config(&tree, &config),
+            "root\n├─ flat\n│  └─ a, b, c\n├─ mixed\n│  ├─ x\n│  └─ sub\n└─ multi\n   ├─ y\n   └─ z1\n      z2\n"
+        );
+        assert!(render_to_string(&tree).contains("│  ├─ a\n│  ├─ b\n│  └─ c\n"));
+    }
+
+    #[test]
+    fn test_join_leaf_siblings_keeps_per_path_options() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node(
+                    "flat".to_string(),
+                    vec![
+                        Tree::Leaf(vec!["a".to_string()]),
+                        Tree::Leaf(vec!["b".to_string()]),
+                    ],
+                ),
+                Tree::Node(
+                    "other".to_string(),
+                    vec![
+                        Tree::Leaf(vec!["c".to_string()]),
+                        Tree::Leaf(vec!["d".to_string()]),
+                    ],
+                ),
+            ],
+        );
+
+        let config = RenderConfig::default().with_join_leaf_siblings(", ");
+        let annotations = HashMap::from([(vec![0, 1], "# note".to_string())]);
+        assert_eq!(
+            render_to_string_with_annotations(&tree, &annotations, &config),
+            "root\n├─ flat\n│  ├─ a\n│  └─ b  # note\n└─ other\n   └─ c, d\n"
+        );
+
+        let config = RenderConfig::default()
+            .with_join_leaf_siblings(", ")
+            .with_glyph_override_fn(|path, _| (path == [1, 1]).then(|| "✔─ ".to_string()));
+        assert_eq!(
+            render_to_string_with_config(&tree, &config),
+            "root\n├─ flat\n│  └─ a, b\n└─ other\n   ├─ c\n   ✔─ d\n"
+        );
+    }
+
+    #[test]
+    fn test_render_forest() {
+        let trees = vec![
+            Tree::Node(
+                "first".to_string(),
+                vec![
+                    Tree::Leaf(vec!["a".to_string()]),
+                    Tree::Node(
+                        "nested".to_string(),
+                        vec![Tree::Leaf(vec!["b".to_string()])],
+                    ),
+                ],
+            ),
+            Tree::Leaf(vec!["loose".to_string()]),
+            Tree::Node(
+                "second".to_string(),
+                vec![Tree::Leaf(vec!["c".to_string()])],
+            ),
+        ];
+        let output = render_forest(&trees, &RenderConfig::default());
+        assert_eq!(
+            output,
+            "first\n├─ a\n└─ nested\n   └─ b\nloose\nsecond\n└─ c\n"
+        );
+        let roots: Vec<&str> = output
+            .lines()
+            .filter(|line| !line.starts_with([' ', '├', '└', '│']))
+            .collect();
+        assert_eq!(roots, vec!["first", "loose", "second"]);
+
+        let config = RenderConfig::default().with_blank_line_between_top_level(true);
+        assert_eq!(
+            render_forest(&trees[1..], &config),
+            "loose\n\nsecond\n└─ c\n"
+        );
+        assert_eq!(render_forest(&[], &config), "");
+    }
+
+    #[test]
+    fn test_blank_line_between_top_level() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node(
+                    "a".to_string(),
+                    vec![
+                        Tree::Leaf(vec!["a1".to_string()]),
+                        Tree::Leaf(vec!["a2".to_string()]),
+                    ],
+                ),
+                Tree::Leaf(vec!["b".to_string()]),
+                Tree::Node("c".to_string(), vec![Tree::Leaf(vec!["c1".to_string()])]),
+            ],
+        );
+        let config = RenderConfig::default().with_blank_line_between_top_level(true);
+        assert_eq!(
+            render_to_string_with_config(&tree, &config),
+            "root\n├─ a\n│  ├─ a1\n│  └─ a2\n\n├─ b\n\n└─ c\n   └─ c1\n"
+        );
+    }
+
+    #[cfg(feature = "color")]
+    #[test]
+    fn test_severity_classifier() {
+        use crate::config::Severity;
+
+        colored::control::set_override(true);
+        let tree = Tree::Node(
+            "log".to_string(),
+            vec![Tree::Leaf(vec![
+                "ERROR disk full".to_string(),
+                "WARN retrying".to_string(),
+                "done".to_string(),
+            ])],
+        );
+        let config = RenderConfig::default()
+            .with_colors(true)
+            .with_severity_classifier(|line| {
+                if line.starts_with("ERROR") {
+                    Severity::Error
+                } else if line.starts_with("WARN") {
+                    Severity::Warning
+                } else {
+                    Severity::Normal
+                }
+            });
+        let output = render_to_string_with_config(&tree, &config);
+        let line = |text: &str| output.lines().find(|l| l.contains(text)).unwrap();
+        let red = "\x1b[31m";
+        assert!(line("ERROR disk full").contains(red));
+        assert!(!line("WARN retrying").contains(red));
+        assert!(line("WARN retrying").contains("\x1b[33m"));
+        assert!(!line("done").contains(red));
+        assert!(line("done").contains("\x1b[32m"));
+    }
+
+    #[cfg(feature = "formatters")]
+    #[test]
+    fn test_empty_nodes_as_leaves() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node("empty".to_string(), vec![]),
+                Tree::Leaf(vec!["item".to_string()]),
+            ],
+        );
+        let config = RenderConfig::default()
+            .with_node_formatter(|label| format!("[{label}]"))
+            .with_leaf_formatter(|line| format!("- {line}"));
+        assert_eq!(
+            render_to_string_with_config(&tree, &config),
+            "[root]\n├─ [empty]\n└─ - item\n"
+        );
+
+        let config = config.with_empty_nodes_as_leaves(true);
+        assert_eq!(
+            render_to_string_with_config(&tree, &config),
+            "[root]\n├─ - empty\n└─ - item\n"
+        );
+    }
+
+    #[cfg(feature = "color")]
+    #[test]
+    fn test_empty_nodes_as_leaves_colors() {
+        colored::control::set_override(true);
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![Tree::Node("empty".to_string(), vec![])],
+        );
+        let config = RenderConfig::default().with_colors(true);
+        let output = render_to_string_with_config(&tree, &config);
+        assert!(output.lines().nth(1).unwrap().contains("\x1b[34m"));
+
+        let config = config.with_empty_nodes_as_leaves(true);
+        let output = render_to_string_with_config(&tree, &config);
+        assert!(output.lines().nth(1).unwrap().contains("\x1b[32m"));
+        assert!(output.lines().next().unwrap().contains("\x1b[34m"));
+    }
+
+    #[test]
+    fn test_glyph_override() {
+        let tree = Tree::Node(
+            "tasks".to_string(),
+            vec![
+                Tree::Node(
+                    "build".to_string(),
+                    vec![Tree::Leaf(vec!["compile".to_string()])],
+                ),
+                Tree::Leaf(vec!["test".to_string()]),
+                Tree::Leaf(vec!["ship".to_string()]),
+            ],
+        );
+        let config = RenderConfig::default()
+            .with_glyph_override_fn(|path, _| (path == [0]).then(|| "✔─ ".to_string()));
+        assert_eq!(
+            render_to_string_with_config(&tree, &config),
+            "tasks\n✔─ build\n│  └─ compile\n├─ test\n└─ ship\n"
+        );
+    }
+
+    #[test]
+    fn test_child_counts() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node(
+                    "dir".to_string(),
+                    vec![
+                        Tree::Leaf(vec!["a".to_string()]),
+                        Tree::Leaf(vec!["b".to_string()]),
+                    ],
+                ),
+                Tree::Leaf(vec!["c".to_string()]),
+            ],
+        );
+        let config = RenderConfig::default().with_child_counts(true);
+        assert_eq!(
+            render_to_string_with_config(&tree, &config),
+            "root (2)\n├─ dir (2)\n│  ├─ a\n│  └─ b\n└─ c\n"
+        );
+
+        let config = config.with_count_mode(CountMode::Descendants);
+        assert_eq!(
+            render_to_string_with_config(&tree, &config),
+            "root (4)\n├─ dir (2)\n│  ├─ a\n│  └─ b\n└─ c\n"
+        );
+    }
+
+    #[test]
+    fn test_align_labels() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node(
+                    "a".to_string(),
+                    vec![Tree::Node(
+                        "b".to_string(),
+                        vec![Tree::Leaf(vec!["deep".to_string(), "more".to_string()])],
+                    )],
+                ),
+                Tree::Leaf(vec!["shallow".to_string()]),
+            ],
+        );
+        let config = RenderConfig::default().with_align_labels(true);
+        let output = render_to_string_with_config(&tree, &config);
+        assert_eq!(
+            output,
+            "         root\n├─────── a\n│  └──── b\n│     └─ deep\n│        more\n└─────── shallow\n"
+        );
+        for line in output.lines() {
+            let column = line.chars().position(char::is_alphanumeric).unwrap();
+            assert_eq!(column, 9, "{line:?}");
+        }
+    }
+
+    #[test]
+    fn test_tab_width_alignment() {
+        let tree = Tree::Node(
+            "config\tfile".to_string(),
+            vec![Tree::Leaf(vec![
+                "a\t= 1".to_string(),
+                "long_key = 2".to_string(),
+            ])],
+        );
+        let config = RenderConfig::default().with_align_on('=');
+        let output = render_to_string_with_config(&tree, &config);
+        // "└─ a" ends at column 4, so the tab reaches column 8
+        assert_eq!(output, "config\tfile\n└─ a\t    = 1\n   long_key = 2\n");
+        let columns: Vec<usize> = output
+            .lines()
+            .skip(1)
+            .map(|line| display_width(&line[..line.find('=').unwrap()], 4, 0))
+            .collect();
+        assert_eq!(columns, vec![12, 12]);
+
+        let config = config.with_expand_tabs(true).with_tab_width(2);
+        assert_eq!(
+            render_to_string_with_config(&tree, &config),
+            "config  file\n└─ a        = 1\n   long_key = 2\n"
+        );
+    }
 }
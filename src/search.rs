@@ -2,6 +2,65 @@
 
 use crate::tree::Tree;
 
+/// Options controlling how labels are matched by [`Tree::find_node_with_options`].
+///
+/// The default matches labels exactly, as [`Tree::find_node`] does.
+///
+/// # Examples
+///
+/// ```
+/// use treelog::SearchOptions;
+///
+/// let options = SearchOptions::default();
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SearchOptions {
+    /// Whether labels and queries are compared after NFC normalization.
+    ///
+    /// Requires the `unicode-normalization` feature.
+    #[cfg(feature = "unicode-normalization")]
+    pub normalize_unicode: bool,
+}
+
+impl SearchOptions {
+    /// Creates search options that match labels exactly.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether labels are compared after NFC normalization.
+    ///
+    /// With normalization, a label stored in decomposed form (NFD) matches a
+    /// query in composed form (NFC) and vice versa.
+    ///
+    /// Requires the `unicode-normalization` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::SearchOptions;
+    ///
+    /// let options = SearchOptions::new().with_normalize_unicode(true);
+    /// assert!(options.normalize_unicode);
+    /// ```
+    #[cfg(feature = "unicode-normalization")]
+    pub fn with_normalize_unicode(mut self, normalize: bool) -> Self {
+        self.normalize_unicode = normalize;
+        self
+    }
+
+    /// Returns whether `label` matches `query` under these options.
+    fn matches(&self, label: &str, query: &str) -> bool {
+        #[cfg(feature = "unicode-normalization")]
+        if self.normalize_unicode {
+            use unicode_normalization::UnicodeNormalization;
+            return label.nfc().eq(query.nfc());
+        }
+        label == query
+    }
+}
+
 impl Tree {
     /// Finds the first node with the given label.
     ///
@@ -19,15 +78,34 @@ impl Tree {
     /// assert!(found.is_some());
     /// ```
     pub fn find_node(&self, label: &str) -> Option<&Tree> {
+        self.find_node_with_options(label, &SearchOptions::default())
+    }
+
+    /// Finds the first node whose label matches `label` under the given options.
+    ///
+    /// Returns `Some(&Tree)` if found, `None` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::{SearchOptions, Tree};
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Node("child".to_string(), vec![])
+    /// ]);
+    /// let found = tree.find_node_with_options("child", &SearchOptions::default());
+    /// assert!(found.is_some());
+    /// ```
+    pub fn find_node_with_options(&self, label: &str, options: &SearchOptions) -> Option<&Tree> {
         if let Tree::Node(node_label, _) = self
-            && node_label == label
+            && options.matches(node_label, label)
         {
             return Some(self);
         }
 
         if let Tree::Node(_, children) = self {
             for child in children {
-                if let Some(found) = child.find_node(label) {
+                if let Some(found) = child.find_node_with_options(label, options) {
                     return Some(found);
                 }
             }
@@ -170,6 +248,94 @@ impl Tree {
         }
     }
 
+    /// Gets the paths (indices) to every node with the given label or leaf containing it.
+    ///
+    /// Paths are returned in pre-order, the locations matching [`find_all_nodes`](Self::find_all_nodes)
+    /// for nodes. Returns an empty vector if nothing matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Node("dup".to_string(), vec![]),
+    ///     Tree::Node("other".to_string(), vec![Tree::Node("dup".to_string(), vec![])])
+    /// ]);
+    /// assert_eq!(tree.paths_to("dup"), vec![vec![0], vec![1, 0]]);
+    /// ```
+    pub fn paths_to(&self, label: &str) -> Vec<Vec<usize>> {
+        let mut results = Vec::new();
+        self.collect_paths(label, &mut Vec::new(), &mut results);
+        results
+    }
+
+    fn collect_paths(&self, label: &str, path: &mut Vec<usize>, results: &mut Vec<Vec<usize>>) {
+        let matches = match self {
+            Tree::Node(node_label, _) => node_label == label,
+            Tree::Leaf(lines) => lines.iter().any(|line| line.contains(label)),
+        };
+        if matches {
+            results.push(path.clone());
+        }
+
+        if let Tree::Node(_, children) = self {
+            for (index, child) in children.iter().enumerate() {
+                path.push(index);
+                child.collect_paths(label, path, results);
+                path.pop();
+            }
+        }
+    }
+
+    /// Finds the most deeply nested element matching the predicate.
+    ///
+    /// Returns the path to the match along with the element itself. When several
+    /// matches share the greatest depth, the first one in pre-order wins.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let tree = Tree::Node("error".to_string(), vec![
+    ///     Tree::Node("ok".to_string(), vec![
+    ///         Tree::Node("error".to_string(), vec![])
+    ///     ])
+    /// ]);
+    /// let (path, found) = tree.find_deepest(|t| t.label() == Some("error")).unwrap();
+    /// assert_eq!(path, vec![0, 0]);
+    /// assert_eq!(found.label(), Some("error"));
+    /// ```
+    pub fn find_deepest<F: Fn(&Tree) -> bool>(&self, pred: F) -> Option<(Vec<usize>, &Tree)> {
+        let mut best = None;
+        self.find_deepest_recursive(&pred, &mut Vec::new(), &mut best);
+        best
+    }
+
+    fn find_deepest_recursive<'a, F: Fn(&Tree) -> bool>(
+        &'a self,
+        pred: &F,
+        path: &mut Vec<usize>,
+        best: &mut Option<(Vec<usize>, &'a Tree)>,
+    ) {
+        if pred(self)
+            && best
+                .as_ref()
+                .is_none_or(|(found, _)| path.len() > found.len())
+        {
+            *best = Some((path.clone(), self));
+        }
+
+        if let Tree::Node(_, children) = self {
+            for (index, child) in children.iter().enumerate() {
+                path.push(index);
+                child.find_deepest_recursive(pred, path, best);
+                path.pop();
+            }
+        }
+    }
+
     fn find_path(&self, label: &str, path: &mut Vec<usize>) -> bool {
         match self {
             Tree::Node(node_label, _) => {
@@ -279,4 +445,88 @@ mod tests {
         let path_not_found = tree.path_to("nonexistent");
         assert_eq!(path_not_found, None);
     }
+
+    #[test]
+    fn test_paths_to() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node(
+                    "dup".to_string(),
+                    vec![Tree::Leaf(vec!["item".to_string()])],
+                ),
+                Tree::Node(
+                    "other".to_string(),
+                    vec![
+                        Tree::Leaf(vec!["first".to_string(), "dup line".to_string()]),
+                        Tree::Node("dup".to_string(), vec![]),
+                    ],
+                ),
+            ],
+        );
+        let paths = tree.paths_to("dup");
+        assert_eq!(paths, vec![vec![0], vec![1, 0], vec![1, 1]]);
+        assert_eq!(tree.paths_to("root"), vec![Vec::<usize>::new()]);
+        assert!(tree.paths_to("nonexistent").is_empty());
+
+        #[cfg(feature = "path")]
+        for path in &paths {
+            let found = tree.get_by_path(path).unwrap();
+            assert!(
+                found.label() == Some("dup")
+                    || found
+                        .lines()
+                        .is_some_and(|lines| lines.iter().any(|l| l.contains("dup")))
+            );
+        }
+    }
+
+    #[test]
+    fn test_find_deepest() {
+        let error = |children| Tree::Node("error".to_string(), children);
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                error(vec![Tree::Leaf(vec!["detail".to_string()])]),
+                Tree::Node(
+                    "mod".to_string(),
+                    vec![
+                        Tree::Node("fn".to_string(), vec![error(vec![])]),
+                        Tree::Node("fn".to_string(), vec![error(vec![])]),
+                    ],
+                ),
+                error(vec![]),
+            ],
+        );
+
+        let is_error = |t: &Tree| t.label() == Some("error");
+        let (path, found) = tree.find_deepest(is_error).unwrap();
+        assert_eq!(path, vec![1, 0, 0]);
+        assert_eq!(found, &error(vec![]));
+
+        assert_eq!(
+            tree.find_deepest(|t| t.label() == Some("root")).unwrap().0,
+            Vec::<usize>::new()
+        );
+        assert!(
+            tree.find_deepest(|t| t.label() == Some("missing"))
+                .is_none()
+        );
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    #[test]
+    fn test_find_node_normalized() {
+        let decomposed = "cafe\u{301}";
+        let composed = "caf\u{e9}";
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![Tree::Node(decomposed.to_string(), vec![])],
+        );
+
+        assert!(tree.find_node(composed).is_none());
+        let options = SearchOptions::new().with_normalize_unicode(true);
+        let found = tree.find_node_with_options(composed, &options);
+        assert_eq!(found.and_then(Tree::label), Some(decomposed));
+    }
 }
@@ -2,6 +2,35 @@
 
 use crate::tree::Tree;
 
+/// Version of the tree representation written by [`Tree::to_json_versioned`].
+const JSON_FORMAT_VERSION: u64 = 1;
+
+/// Document written by [`Tree::to_json_versioned`], with the version first.
+#[derive(serde::Serialize)]
+struct VersionedTree<'a> {
+    version: u64,
+    tree: &'a Tree,
+}
+
+/// Converts a tree serialized in format `version` to the current representation.
+///
+/// When the representation of [`Tree`] changes, bump [`JSON_FORMAT_VERSION`] and
+/// add an arm here rewriting the older shape, so documents written by earlier
+/// releases keep loading.
+fn migrate_json(
+    version: u64,
+    tree: serde_json::Value,
+) -> Result<serde_json::Value, serde_json::Error> {
+    use serde::de::Error;
+
+    match version {
+        JSON_FORMAT_VERSION => Ok(tree),
+        _ => Err(serde_json::Error::custom(format!(
+            "unsupported tree JSON version {version}, expected {JSON_FORMAT_VERSION}"
+        ))),
+    }
+}
+
 impl Tree {
     /// Deserializes a tree from JSON.
     ///
@@ -55,6 +84,145 @@ impl Tree {
     pub fn to_json_pretty(&self) -> Result<String, serde_json::Error> {
         serde_json::to_string_pretty(self)
     }
+
+    /// Serializes the tree to JSON wrapped with a format version.
+    ///
+    /// The output is `{"version":1,"tree":...}`, where `tree` holds the same
+    /// representation as [`to_json`](Self::to_json). Read it back with
+    /// [`from_json_versioned`](Self::from_json_versioned).
+    ///
+    /// Requires the `serde-json` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![Tree::Leaf(vec!["item".to_string()])]);
+    /// let json = tree.to_json_versioned();
+    /// assert!(json.starts_with(r#"{"version":1,"tree":"#));
+    /// ```
+    pub fn to_json_versioned(&self) -> String {
+        serde_json::to_string(&VersionedTree {
+            version: JSON_FORMAT_VERSION,
+            tree: self,
+        })
+        .expect("trees always serialize to JSON")
+    }
+
+    /// Deserializes a tree written by [`to_json_versioned`](Self::to_json_versioned).
+    ///
+    /// Returns an error if the input has no numeric `version` or `tree`, or if the
+    /// version is not one this release can read.
+    ///
+    /// Requires the `serde-json` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![Tree::Leaf(vec!["item".to_string()])]);
+    /// assert_eq!(Tree::from_json_versioned(&tree.to_json_versioned()).unwrap(), tree);
+    ///
+    /// let future = r#"{"version":99,"tree":{"Leaf":["item"]}}"#;
+    /// assert!(Tree::from_json_versioned(future).is_err());
+    /// ```
+    pub fn from_json_versioned(json: &str) -> Result<Self, serde_json::Error> {
+        use serde::de::Error;
+
+        let mut document: serde_json::Value = serde_json::from_str(json)?;
+        let version = document
+            .get("version")
+            .and_then(serde_json::Value::as_u64)
+            .ok_or_else(|| {
+                serde_json::Error::custom("versioned tree JSON requires a numeric `version`")
+            })?;
+        let tree = document
+            .get_mut("tree")
+            .map(serde_json::Value::take)
+            .ok_or_else(|| serde_json::Error::custom("versioned tree JSON requires a `tree`"))?;
+        serde_json::from_value(migrate_json(version, tree)?)
+    }
+
+    /// Parses the nested `{name, children}` UI format produced by [`Tree::to_ui_json`].
+    ///
+    /// Objects with a non-empty `children` array become nodes; objects without
+    /// `children` (or with an empty array) become leaves, with the name split on `\n`
+    /// into lines. Returns an error if an object has no string `name` or `children`
+    /// is not an array.
+    ///
+    /// Requires the `serde-json` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let value = serde_json::json!({
+    ///     "name": "root",
+    ///     "children": [{ "name": "item" }]
+    /// });
+    /// let tree = Tree::from_ui_json(&value).unwrap();
+    /// assert_eq!(tree, Tree::Node("root".to_string(), vec![Tree::Leaf(vec!["item".to_string()])]));
+    /// ```
+    pub fn from_ui_json(value: &serde_json::Value) -> Result<Self, serde_json::Error> {
+        use serde::de::Error;
+
+        let name = value
+            .get("name")
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| serde_json::Error::custom("UI tree object requires a string `name`"))?;
+        let children = match value.get("children") {
+            None | Some(serde_json::Value::Null) => &[][..],
+            Some(serde_json::Value::Array(children)) => children.as_slice(),
+            Some(_) => {
+                return Err(serde_json::Error::custom(
+                    "UI tree `children` must be an array",
+                ));
+            }
+        };
+
+        if children.is_empty() {
+            Ok(Tree::Leaf(name.split('\n').map(str::to_string).collect()))
+        } else {
+            let children = children
+                .iter()
+                .map(Tree::from_ui_json)
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Tree::Node(name.to_string(), children))
+        }
+    }
+
+    /// Converts the tree to the nested `{name, children}` form used by UI tree components.
+    ///
+    /// Nodes become `{"name": label, "children": [...]}` and leaves become
+    /// `{"name": text, "isLeaf": true}`, where multi-line leaves join their lines with `\n`.
+    ///
+    /// Requires the `serde-json` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![Tree::Leaf(vec!["item".to_string()])]);
+    /// let value = tree.to_ui_json();
+    /// assert_eq!(value["name"], "root");
+    /// assert_eq!(value["children"][0]["isLeaf"], true);
+    /// ```
+    pub fn to_ui_json(&self) -> serde_json::Value {
+        match self {
+            Tree::Node(label, children) => serde_json::json!({
+                "name": label,
+                "children": children.iter().map(Tree::to_ui_json).collect::<Vec<_>>(),
+            }),
+            Tree::Leaf(lines) => serde_json::json!({
+                "name": lines.join("\n"),
+                "isLeaf": true,
+            }),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -77,4 +245,90 @@ mod tests {
         let deserialized = Tree::from_json(&json).unwrap();
         assert_eq!(tree, deserialized);
     }
+
+    #[test]
+    fn test_json_versioned_roundtrip() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Leaf(vec!["item1".to_string()]),
+                Tree::Node(
+                    "sub".to_string(),
+                    vec![Tree::Leaf(vec!["line1".to_string(), "line2".to_string()])],
+                ),
+            ],
+        );
+        let json = tree.to_json_versioned();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["version"], 1);
+        assert_eq!(
+            value["tree"],
+            serde_json::from_str::<serde_json::Value>(&tree.to_json().unwrap()).unwrap()
+        );
+        assert_eq!(Tree::from_json_versioned(&json).unwrap(), tree);
+    }
+
+    #[test]
+    fn test_json_versioned_rejects_other_versions() {
+        let tree = Tree::new_leaf("item");
+        let future = tree
+            .to_json_versioned()
+            .replace(r#""version":1"#, r#""version":2"#);
+        let error = Tree::from_json_versioned(&future).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("unsupported tree JSON version 2")
+        );
+
+        let unversioned = tree.to_json().unwrap();
+        assert!(Tree::from_json_versioned(&unversioned).is_err());
+        assert!(Tree::from_json_versioned(r#"{"version":1}"#).is_err());
+    }
+
+    #[test]
+    fn test_to_ui_json() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Leaf(vec!["item1".to_string()]),
+                Tree::Node(
+                    "sub".to_string(),
+                    vec![Tree::Leaf(vec!["line1".to_string(), "line2".to_string()])],
+                ),
+            ],
+        );
+        let expected = serde_json::json!({
+            "name": "root",
+            "children": [
+                { "name": "item1", "isLeaf": true },
+                {
+                    "name": "sub",
+                    "children": [{ "name": "line1\nline2", "isLeaf": true }]
+                }
+            ]
+        });
+        assert_eq!(tree.to_ui_json(), expected);
+    }
+
+    #[test]
+    fn test_ui_json_roundtrip() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Leaf(vec!["item1".to_string()]),
+                Tree::Node(
+                    "sub".to_string(),
+                    vec![Tree::Leaf(vec!["line1".to_string(), "line2".to_string()])],
+                ),
+            ],
+        );
+        assert_eq!(Tree::from_ui_json(&tree.to_ui_json()).unwrap(), tree);
+
+        let empty = serde_json::json!({ "name": "empty", "children": [] });
+        assert_eq!(Tree::from_ui_json(&empty).unwrap(), Tree::new_leaf("empty"));
+
+        assert!(Tree::from_ui_json(&serde_json::json!({ "children": [] })).is_err());
+        assert!(Tree::from_ui_json(&serde_json::json!({ "name": "x", "children": 1 })).is_err());
+    }
 }
@@ -42,6 +42,35 @@ impl Tree {
         }
     }
 
+    /// Sorts children at each level using a copyable comparison function.
+    ///
+    /// This recursively sorts all children throughout the tree, and is what the
+    /// built-in orderings such as [`sort_by_label`](Self::sort_by_label) use.
+    /// Each node's children are sorted with a fresh copy of `cmp`; use
+    /// [`sort_children`](Self::sort_children) for a comparator that keeps state
+    /// across the whole tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let mut tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Node("longer".to_string(), vec![]),
+    ///     Tree::Node("short".to_string(), vec![]),
+    /// ]);
+    /// tree.sort_all_by(|a, b| a.label().map(str::len).cmp(&b.label().map(str::len)));
+    /// assert_eq!(tree.children().unwrap()[0].label(), Some("short"));
+    /// ```
+    pub fn sort_all_by(&mut self, mut cmp: impl FnMut(&Tree, &Tree) -> std::cmp::Ordering + Copy) {
+        if let Tree::Node(_, children) = self {
+            children.sort_by(&mut cmp);
+            for child in children.iter_mut() {
+                child.sort_all_by(cmp);
+            }
+        }
+    }
+
     /// Sorts children alphabetically by label (for nodes) or first line (for leaves).
     ///
     /// This recursively sorts all children throughout the tree.
@@ -58,18 +87,47 @@ impl Tree {
     /// tree.sort_by_label();
     /// ```
     pub fn sort_by_label(&mut self) {
-        let mut compare = |a: &Tree, b: &Tree| {
-            let label_a = match a {
-                Tree::Node(label, _) => label.as_str(),
-                Tree::Leaf(lines) => lines.first().map(|s| s.as_str()).unwrap_or(""),
-            };
-            let label_b = match b {
-                Tree::Node(label, _) => label.as_str(),
-                Tree::Leaf(lines) => lines.first().map(|s| s.as_str()).unwrap_or(""),
-            };
-            label_a.cmp(label_b)
-        };
-        self.sort_children(&mut compare);
+        self.sort_all_by(|a, b| a.sort_label().cmp(b.sort_label()));
+    }
+
+    /// Inserts a child at the position that keeps this node's children sorted by label.
+    ///
+    /// Children are compared the same way as [`Tree::sort_by_label`] (label for nodes,
+    /// first line for leaves), and the position is found by binary search, so the
+    /// existing children must already be sorted. A child whose label equals existing
+    /// ones is inserted after them.
+    ///
+    /// Returns `Some(self)` if this is a node, `None` (leaving the tree unchanged) if it's a leaf.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let mut tree = Tree::new_node("root");
+    /// tree.insert_child_sorted(Tree::new_leaf("c"));
+    /// tree.insert_child_sorted(Tree::new_leaf("a"));
+    /// tree.insert_child_sorted(Tree::new_node("b"));
+    /// assert_eq!(tree.children().unwrap()[1].label(), Some("b"));
+    /// ```
+    pub fn insert_child_sorted(&mut self, child: Tree) -> Option<&mut Self> {
+        match self {
+            Tree::Node(_, children) => {
+                let key = child.sort_label();
+                let index = children.partition_point(|existing| existing.sort_label() <= key);
+                children.insert(index, child);
+                Some(self)
+            }
+            Tree::Leaf(_) => None,
+        }
+    }
+
+    /// Returns the label used for label ordering: the label for nodes, the first line for leaves.
+    fn sort_label(&self) -> &str {
+        match self {
+            Tree::Node(label, _) => label.as_str(),
+            Tree::Leaf(lines) => lines.first().map(|s| s.as_str()).unwrap_or(""),
+        }
     }
 
     /// Sorts children by depth, with the deepest first or last.
@@ -92,7 +150,7 @@ impl Tree {
     ///
     /// Note: This method requires the `stats` feature to be enabled.
     pub fn sort_by_depth(&mut self, deepest_first: bool) {
-        let mut compare = |a: &Tree, b: &Tree| {
+        self.sort_all_by(|a, b| {
             let depth_a = a.depth();
             let depth_b = b.depth();
             if deepest_first {
@@ -100,8 +158,41 @@ impl Tree {
             } else {
                 depth_a.cmp(&depth_b)
             }
-        };
-        self.sort_children(&mut compare);
+        });
+    }
+
+    /// Sorts children by their full content hash, giving a canonical sibling order.
+    ///
+    /// This recursively sorts all children throughout the tree, innermost first,
+    /// so subtrees are compared after their own children are in canonical order.
+    /// Unlike [`sort_by_label`](Self::sort_by_label), the order accounts for
+    /// entire subtrees, so trees that differ only in sibling order sort to the
+    /// same result. The order follows [`content_hash`](Self::content_hash) and is
+    /// stable across runs, but is otherwise not meaningful.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let a = Tree::Leaf(vec!["a".to_string()]);
+    /// let b = Tree::Leaf(vec!["b".to_string()]);
+    /// let mut first = Tree::Node("root".to_string(), vec![a.clone(), b.clone()]);
+    /// let mut second = Tree::Node("root".to_string(), vec![b, a]);
+    /// first.sort_by_content();
+    /// second.sort_by_content();
+    /// assert_eq!(first, second);
+    /// ```
+    ///
+    /// Note: This method requires the `hash` feature to be enabled.
+    #[cfg(feature = "hash")]
+    pub fn sort_by_content(&mut self) {
+        if let Tree::Node(_, children) = self {
+            for child in children.iter_mut() {
+                child.sort_by_content();
+            }
+            children.sort_by_cached_key(Tree::content_hash);
+        }
     }
 }
 
@@ -131,6 +222,45 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "stats")]
+    #[test]
+    fn test_sort_all_by_leaf_count() {
+        let leaves = |count: usize| {
+            (0..count)
+                .map(|i| Tree::Leaf(vec![i.to_string()]))
+                .collect()
+        };
+        let mut tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node("three".to_string(), leaves(3)),
+                Tree::Node(
+                    "one".to_string(),
+                    vec![
+                        Tree::Node("two".to_string(), leaves(2)),
+                        Tree::Node("none".to_string(), vec![]),
+                    ],
+                ),
+                Tree::Leaf(vec!["single".to_string()]),
+            ],
+        );
+        tree.sort_all_by(|a, b| a.leaf_count().cmp(&b.leaf_count()));
+
+        let labels = |tree: &Tree| -> Vec<String> {
+            tree.children()
+                .unwrap()
+                .iter()
+                .map(|child| {
+                    child
+                        .label()
+                        .map_or_else(|| child.lines().unwrap()[0].clone(), str::to_string)
+                })
+                .collect()
+        };
+        assert_eq!(labels(&tree), vec!["single", "one", "three"]);
+        assert_eq!(labels(&tree.children().unwrap()[1]), vec!["none", "two"]);
+    }
+
     #[test]
     fn test_sort_by_label() {
         let mut tree = Tree::Node(
@@ -147,6 +277,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_insert_child_sorted() {
+        let mut tree = Tree::new_node("root");
+        for label in ["d", "b", "e", "a", "c"] {
+            tree.insert_child_sorted(Tree::new_leaf(label));
+        }
+        tree.insert_child_sorted(Tree::new_node("bb"));
+
+        let labels: Vec<&str> = tree
+            .children()
+            .unwrap()
+            .iter()
+            .map(|child| child.sort_label())
+            .collect();
+        assert_eq!(labels, vec!["a", "b", "bb", "c", "d", "e"]);
+
+        let mut leaf = Tree::new_leaf("leaf");
+        assert!(leaf.insert_child_sorted(Tree::new_leaf("child")).is_none());
+        assert_eq!(leaf, Tree::new_leaf("leaf"));
+    }
+
     #[cfg(feature = "stats")]
     #[test]
     fn test_sort_by_depth() {
@@ -166,4 +317,36 @@ mod tests {
             assert!(children[1].is_leaf());
         }
     }
+
+    #[cfg(feature = "hash")]
+    #[test]
+    fn test_sort_by_content() {
+        let leaf = |text: &str| Tree::Leaf(vec![text.to_string()]);
+        let dir = |children: Vec<Tree>| Tree::Node("dir".to_string(), children);
+        let mut first = Tree::Node(
+            "root".to_string(),
+            vec![
+                dir(vec![leaf("a"), leaf("b")]),
+                dir(vec![leaf("c")]),
+                leaf("x"),
+            ],
+        );
+        let mut second = Tree::Node(
+            "root".to_string(),
+            vec![
+                leaf("x"),
+                dir(vec![leaf("c")]),
+                dir(vec![leaf("b"), leaf("a")]),
+            ],
+        );
+        assert_ne!(first, second);
+        first.sort_by_content();
+        second.sort_by_content();
+        assert_eq!(first, second);
+        assert_eq!(first.render_to_string(), second.render_to_string());
+
+        let sorted = first.clone();
+        first.sort_by_content();
+        assert_eq!(first, sorted);
+    }
 }
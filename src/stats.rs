@@ -1,5 +1,7 @@
 //! Tree statistics and metrics.
 
+use std::collections::{BTreeMap, BTreeSet};
+
 use crate::tree::Tree;
 
 /// Statistics about a tree structure.
@@ -28,6 +30,39 @@ pub struct TreeStats {
     pub leaf_count: usize,
     /// Total number of lines across all leaves
     pub total_lines: usize,
+    /// (node count, leaf count) at each depth, with the root at index 0
+    #[cfg_attr(
+        any(
+            feature = "serde",
+            feature = "serde-json",
+            feature = "serde-yaml",
+            feature = "serde-toml",
+            feature = "serde-ron"
+        ),
+        serde(default)
+    )]
+    pub breakdown: Vec<(usize, usize)>,
+}
+
+impl TreeStats {
+    /// Returns the number of nodes and leaves at each depth, indexed by depth.
+    ///
+    /// Each entry is `(node_count, leaf_count)`; the root is at index 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Node("child".to_string(), vec![]),
+    ///     Tree::Leaf(vec!["item".to_string()])
+    /// ]);
+    /// assert_eq!(tree.stats().depth_breakdown(), vec![(1, 0), (1, 1)]);
+    /// ```
+    pub fn depth_breakdown(&self) -> &[(usize, usize)] {
+        &self.breakdown
+    }
 }
 
 impl Tree {
@@ -170,6 +205,35 @@ impl Tree {
         }
     }
 
+    /// Returns the number of lines the tree renders to with the default configuration.
+    ///
+    /// Each node contributes one line and each leaf one line per text line, so
+    /// this matches the length of the rendered output without rendering it.
+    /// Options that add or hide lines, such as collapsed nodes, are not accounted for.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["line1".to_string(), "line2".to_string()]),
+    ///     Tree::Node("child".to_string(), vec![]),
+    /// ]);
+    /// assert_eq!(tree.rendered_line_count(), 4);
+    /// ```
+    pub fn rendered_line_count(&self) -> usize {
+        match self {
+            Tree::Node(_, children) => {
+                1 + children
+                    .iter()
+                    .map(|child| child.rendered_line_count())
+                    .sum::<usize>()
+            }
+            Tree::Leaf(lines) => lines.len(),
+        }
+    }
+
     /// Returns statistics about the tree.
     ///
     /// # Examples
@@ -192,8 +256,142 @@ impl Tree {
             node_count: self.node_count(),
             leaf_count: self.leaf_count(),
             total_lines: self.total_lines(),
+            breakdown: self.depth_breakdown(),
         }
     }
+
+    fn depth_breakdown(&self) -> Vec<(usize, usize)> {
+        let mut breakdown = Vec::new();
+        self.collect_depth_breakdown(0, &mut breakdown);
+        breakdown
+    }
+
+    fn collect_depth_breakdown(&self, depth: usize, breakdown: &mut Vec<(usize, usize)>) {
+        if breakdown.len() <= depth {
+            breakdown.push((0, 0));
+        }
+        match self {
+            Tree::Node(_, children) => {
+                breakdown[depth].0 += 1;
+                for child in children {
+                    child.collect_depth_breakdown(depth + 1, breakdown);
+                }
+            }
+            Tree::Leaf(_) => breakdown[depth].1 += 1,
+        }
+    }
+
+    /// Returns statistics about the subtree at `path`, or `None` if the path is invalid.
+    ///
+    /// The path is a sequence of child indices from the root; the empty path
+    /// yields the same result as [`stats`](Self::stats).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Node("child".to_string(), vec![
+    ///         Tree::Leaf(vec!["item".to_string()])
+    ///     ])
+    /// ]);
+    /// assert_eq!(tree.stats_at(&[0]).unwrap().leaf_count, 1);
+    /// assert!(tree.stats_at(&[1]).is_none());
+    /// ```
+    pub fn stats_at(&self, path: &[usize]) -> Option<TreeStats> {
+        self.stats_subtree(path).map(Tree::stats)
+    }
+
+    /// Returns the total number of leaf lines in the subtree at `path`, or `None`
+    /// if the path is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Node("child".to_string(), vec![
+    ///         Tree::Leaf(vec!["line1".to_string(), "line2".to_string()])
+    ///     ]),
+    ///     Tree::Leaf(vec!["other".to_string()])
+    /// ]);
+    /// assert_eq!(tree.line_count_at(&[0]), Some(2));
+    /// assert_eq!(tree.line_count_at(&[2]), None);
+    /// ```
+    pub fn line_count_at(&self, path: &[usize]) -> Option<usize> {
+        self.stats_subtree(path).map(Tree::total_lines)
+    }
+
+    /// Returns the set of distinct node labels in the tree.
+    ///
+    /// Only node labels are collected; leaf lines are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Node("item".to_string(), vec![]),
+    ///     Tree::Node("item".to_string(), vec![])
+    /// ]);
+    /// let labels: Vec<String> = tree.distinct_labels().into_iter().collect();
+    /// assert_eq!(labels, vec!["item", "root"]);
+    /// ```
+    pub fn distinct_labels(&self) -> BTreeSet<String> {
+        self.label_counts()
+            .into_keys()
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Returns the node labels that occur more than once, with their counts.
+    ///
+    /// Labels are returned in sorted order. Only node labels are counted; leaf
+    /// lines are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Node("item".to_string(), vec![]),
+    ///     Tree::Node("item".to_string(), vec![])
+    /// ]);
+    /// assert_eq!(tree.repeated_labels(), vec![("item".to_string(), 2)]);
+    /// ```
+    pub fn repeated_labels(&self) -> Vec<(String, usize)> {
+        self.label_counts()
+            .into_iter()
+            .filter(|&(_, count)| count > 1)
+            .map(|(label, count)| (label.to_string(), count))
+            .collect()
+    }
+
+    fn label_counts(&self) -> BTreeMap<&str, usize> {
+        fn count<'a>(tree: &'a Tree, counts: &mut BTreeMap<&'a str, usize>) {
+            if let Tree::Node(label, children) = tree {
+                *counts.entry(label.as_str()).or_insert(0) += 1;
+                for child in children {
+                    count(child, counts);
+                }
+            }
+        }
+        let mut counts = BTreeMap::new();
+        count(self, &mut counts);
+        counts
+    }
+
+    fn stats_subtree(&self, path: &[usize]) -> Option<&Tree> {
+        let mut subtree = self;
+        for &index in path {
+            subtree = subtree.children()?.get(index)?;
+        }
+        Some(subtree)
+    }
 }
 
 #[cfg(test)]
@@ -324,6 +522,39 @@ mod tests {
         assert_eq!(leaf.total_lines(), 1);
     }
 
+    #[test]
+    fn test_rendered_line_count() {
+        let trees = [
+            Tree::Leaf(vec![]),
+            Tree::Leaf(vec!["a".to_string(), "b".to_string(), "c".to_string()]),
+            Tree::Node("root".to_string(), vec![]),
+            Tree::Node(
+                "root".to_string(),
+                vec![
+                    Tree::Leaf(vec!["one".to_string(), "two".to_string()]),
+                    Tree::Leaf(vec![]),
+                    Tree::Node(
+                        "child".to_string(),
+                        vec![
+                            Tree::Leaf(vec!["x".to_string()]),
+                            Tree::Leaf(vec!["y1".to_string(), "y2".to_string(), "y3".to_string()]),
+                        ],
+                    ),
+                ],
+            ),
+        ];
+        let expected = [0, 3, 1, 8];
+        for (tree, expected) in trees.iter().zip(expected) {
+            assert_eq!(tree.rendered_line_count(), expected);
+            assert_eq!(tree.render_to_string().lines().count(), expected);
+            #[cfg(feature = "iterator")]
+            assert_eq!(
+                crate::iterator::TreeIteratorExt::to_lines(tree).len(),
+                expected
+            );
+        }
+    }
+
     #[test]
     fn test_stats() {
         let tree = Tree::Node(
@@ -343,4 +574,118 @@ mod tests {
         assert_eq!(stats.total_lines, 2);
         assert_eq!(stats.width, 2);
     }
+
+    #[test]
+    fn test_stats_at() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node(
+                    "child".to_string(),
+                    vec![
+                        Tree::Leaf(vec!["a".to_string(), "b".to_string()]),
+                        Tree::Node(
+                            "nested".to_string(),
+                            vec![Tree::Leaf(vec!["c".to_string()])],
+                        ),
+                    ],
+                ),
+                Tree::Leaf(vec!["leaf".to_string()]),
+            ],
+        );
+        let subtree = &tree.children().unwrap()[0];
+        assert_eq!(tree.stats_at(&[0]), Some(subtree.stats()));
+        assert_eq!(tree.stats_at(&[]), Some(tree.stats()));
+        assert_eq!(tree.stats_at(&[1]).unwrap().leaf_count, 1);
+        assert_eq!(tree.stats_at(&[2]), None);
+        assert_eq!(tree.stats_at(&[1, 0]), None);
+    }
+
+    #[test]
+    fn test_line_count_at() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node(
+                    "child".to_string(),
+                    vec![
+                        Tree::Leaf(vec!["a".to_string(), "b".to_string()]),
+                        Tree::Node(
+                            "nested".to_string(),
+                            vec![Tree::Leaf(vec!["c".to_string()])],
+                        ),
+                    ],
+                ),
+                Tree::Leaf(vec!["leaf".to_string()]),
+            ],
+        );
+        let subtree = &tree.children().unwrap()[0];
+        assert_eq!(tree.line_count_at(&[0]), Some(subtree.total_lines()));
+        assert_eq!(tree.line_count_at(&[0]), Some(3));
+        assert_eq!(tree.line_count_at(&[]), Some(tree.total_lines()));
+        assert_eq!(tree.line_count_at(&[0, 1, 0]), Some(1));
+        assert_eq!(tree.line_count_at(&[0, 5]), None);
+    }
+
+    #[test]
+    fn test_depth_breakdown() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node(
+                    "a".to_string(),
+                    vec![
+                        Tree::Leaf(vec!["a1".to_string()]),
+                        Tree::Node("a2".to_string(), vec![Tree::Leaf(vec!["deep".to_string()])]),
+                    ],
+                ),
+                Tree::Node("b".to_string(), vec![Tree::Leaf(vec!["b1".to_string()])]),
+                Tree::Leaf(vec!["c".to_string()]),
+            ],
+        );
+        assert_eq!(
+            tree.stats().depth_breakdown(),
+            vec![(1, 0), (2, 1), (1, 2), (0, 1)]
+        );
+        assert_eq!(
+            Tree::Leaf(vec!["x".to_string()]).stats().depth_breakdown(),
+            vec![(0, 1)]
+        );
+    }
+
+    #[test]
+    fn test_distinct_and_repeated_labels() {
+        let record = |name: &str| {
+            Tree::Node(
+                "record".to_string(),
+                vec![
+                    Tree::Node("name".to_string(), vec![Tree::Leaf(vec![name.to_string()])]),
+                    Tree::Node("id".to_string(), vec![Tree::Leaf(vec!["1".to_string()])]),
+                ],
+            )
+        };
+        let tree = Tree::Node(
+            "records".to_string(),
+            vec![
+                record("a"),
+                record("b"),
+                record("c"),
+                Tree::Node("meta".to_string(), vec![]),
+            ],
+        );
+        let distinct: Vec<String> = tree.distinct_labels().into_iter().collect();
+        assert_eq!(distinct, vec!["id", "meta", "name", "record", "records"]);
+        assert_eq!(
+            tree.repeated_labels(),
+            vec![
+                ("id".to_string(), 3),
+                ("name".to_string(), 3),
+                ("record".to_string(), 3),
+            ]
+        );
+
+        let leaf = Tree::Leaf(vec!["x".to_string()]);
+        assert!(leaf.distinct_labels().is_empty());
+        assert!(leaf.repeated_labels().is_empty());
+    }
 }
@@ -88,6 +88,17 @@ impl TreeStyle {
 /// };
 /// ```
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    any(
+        feature = "serde",
+        feature = "serde-json",
+        feature = "serde-yaml",
+        feature = "serde-toml",
+        feature = "serde-ron"
+    ),
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct StyleConfig {
     /// Character sequence for a branch (non-last child)
     pub branch: String,
@@ -1,5 +1,8 @@
 //! Tree transformation operations.
 
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
 use crate::tree::Tree;
 
 /// Extension methods for Tree that provide transformation operations.
@@ -59,13 +62,36 @@ impl Tree {
     pub fn map_leaves<F>(&self, f: F) -> Tree
     where
         F: Fn(&str) -> String,
+    {
+        Self::map_leaves_impl(self, &|_, line: &str| f(line))
+    }
+
+    /// Transforms all leaf lines using the given function, passing each line's index.
+    ///
+    /// The index is the position of the line within its leaf, so `0` is the first
+    /// line and higher indices are continuation lines.
+    /// Returns a new tree with transformed leaf lines.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["first".to_string(), "second".to_string()])
+    /// ]);
+    /// let numbered = tree.map_leaves_indexed(|i, line| format!("{}: {}", i + 1, line));
+    /// ```
+    pub fn map_leaves_indexed<F>(&self, f: F) -> Tree
+    where
+        F: Fn(usize, &str) -> String,
     {
         Self::map_leaves_impl(self, &f)
     }
 
     fn map_leaves_impl<F>(tree: &Tree, f: &F) -> Tree
     where
-        F: Fn(&str) -> String,
+        F: Fn(usize, &str) -> String,
     {
         match tree {
             Tree::Node(label, children) => {
@@ -76,12 +102,47 @@ impl Tree {
                 Tree::Node(label.clone(), new_children)
             }
             Tree::Leaf(lines) => {
-                let new_lines = lines.iter().map(|line| f(line)).collect();
+                let new_lines = lines
+                    .iter()
+                    .enumerate()
+                    .map(|(index, line)| f(index, line))
+                    .collect();
                 Tree::Leaf(new_lines)
             }
         }
     }
 
+    /// Renames the first node (in pre-order) whose label equals `from` to `to`.
+    ///
+    /// Unlike `map_nodes`, this mutates the tree in place and touches at most one node.
+    /// Returns `true` if a node was renamed, `false` if no node matched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let mut tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Node("child".to_string(), vec![])
+    /// ]);
+    /// assert!(tree.rename_first("child", "renamed"));
+    /// assert!(!tree.rename_first("missing", "renamed"));
+    /// ```
+    pub fn rename_first(&mut self, from: &str, to: &str) -> bool {
+        match self {
+            Tree::Node(label, children) => {
+                if label == from {
+                    *label = to.to_string();
+                    return true;
+                }
+                children
+                    .iter_mut()
+                    .any(|child| child.rename_first(from, to))
+            }
+            Tree::Leaf(_) => false,
+        }
+    }
+
     /// Filters the tree structure, keeping only nodes/leaves that match the predicate.
     ///
     /// If a node's children are all filtered out, the node itself is also removed.
@@ -138,6 +199,60 @@ impl Tree {
         }
     }
 
+    /// Reduces the tree to the elements matching the predicate and their ancestors.
+    ///
+    /// An element is kept if it matches or has a matching descendant; branches
+    /// without any match are dropped. Unlike [`filter`](Self::filter), a matching
+    /// node keeps its entire subtree, so search results show the matched node
+    /// with its contents. Returns `None` if nothing matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Node("src".to_string(), vec![
+    ///         Tree::Leaf(vec!["main.rs".to_string()]),
+    ///         Tree::Leaf(vec!["lib.rs".to_string()])
+    ///     ]),
+    ///     Tree::Leaf(vec!["README".to_string()])
+    /// ]);
+    /// let matches = tree.filter_to_matches(|t| t.lines().is_some_and(|l| l[0] == "lib.rs"));
+    /// assert_eq!(matches, Some(Tree::Node("root".to_string(), vec![
+    ///     Tree::Node("src".to_string(), vec![Tree::Leaf(vec!["lib.rs".to_string()])])
+    /// ])));
+    /// ```
+    pub fn filter_to_matches<F>(&self, predicate: F) -> Option<Tree>
+    where
+        F: Fn(&Tree) -> bool,
+    {
+        Self::filter_to_matches_impl(self, &predicate)
+    }
+
+    fn filter_to_matches_impl<F>(tree: &Tree, predicate: &F) -> Option<Tree>
+    where
+        F: Fn(&Tree) -> bool,
+    {
+        if predicate(tree) {
+            return Some(tree.clone());
+        }
+        match tree {
+            Tree::Node(label, children) => {
+                let matched_children: Vec<Tree> = children
+                    .iter()
+                    .filter_map(|child| Self::filter_to_matches_impl(child, predicate))
+                    .collect();
+                if matched_children.is_empty() {
+                    None
+                } else {
+                    Some(Tree::Node(label.clone(), matched_children))
+                }
+            }
+            Tree::Leaf(_) => None,
+        }
+    }
+
     /// Prunes the tree by removing nodes/leaves that match the predicate.
     ///
     /// This is the inverse of `filter` - it removes matching items instead of keeping them.
@@ -166,6 +281,237 @@ impl Tree {
         // Prune is the inverse of filter
         self.filter(|t| !predicate(t))
     }
+
+    /// Returns a canonical normal form of the tree, for caching and comparison.
+    ///
+    /// Each node is normalized bottom-up, applying these steps in order:
+    ///
+    /// 1. Trim whitespace from the label and from every leaf line, dropping leaf
+    ///    lines that become empty.
+    /// 2. Canonicalize all children.
+    /// 3. Drop children that are empty: leaves without lines and nodes without children.
+    /// 4. Coalesce all remaining leaf children into a single leaf, concatenating
+    ///    their lines with the leaves ordered by their lines.
+    /// 5. Sort the children: the coalesced leaf first, then nodes by label, with ties
+    ///    broken by comparing their canonical children.
+    ///
+    /// The root is never dropped. Trees that differ only in child order, leaf
+    /// grouping, surrounding whitespace, or empty nodes canonicalize to the same value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let a = Tree::Node("root".to_string(), vec![
+    ///     Tree::Node(" dir ".to_string(), vec![]),
+    ///     Tree::Leaf(vec!["b".to_string()]),
+    ///     Tree::Leaf(vec!["a".to_string()])
+    /// ]);
+    /// let b = Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["a ".to_string(), "b".to_string()])
+    /// ]);
+    /// assert_eq!(a.canonicalize(), b.canonicalize());
+    /// ```
+    pub fn canonicalize(&self) -> Tree {
+        match self {
+            Tree::Leaf(lines) => Tree::Leaf(canonical_lines(lines)),
+            Tree::Node(label, children) => {
+                let mut leaves: Vec<Vec<String>> = Vec::new();
+                let mut nodes: Vec<Tree> = Vec::new();
+                for child in children.iter().map(Tree::canonicalize) {
+                    match child {
+                        Tree::Leaf(lines) if !lines.is_empty() => leaves.push(lines),
+                        Tree::Node(_, ref grandchildren) if !grandchildren.is_empty() => {
+                            nodes.push(child)
+                        }
+                        _ => {}
+                    }
+                }
+
+                leaves.sort();
+                nodes.sort_by(canonical_cmp);
+                let mut canonical = Vec::with_capacity(nodes.len() + 1);
+                if !leaves.is_empty() {
+                    canonical.push(Tree::Leaf(leaves.concat()));
+                }
+                canonical.extend(nodes);
+                Tree::Node(label.trim().to_string(), canonical)
+            }
+        }
+    }
+
+    /// Normalizes every label and leaf line to Unicode Normalization Form C (NFC).
+    ///
+    /// Text that looks identical can be stored in different forms, such as an
+    /// accented letter as one code point or as a base letter plus a combining
+    /// mark. After normalization such labels compare equal in search, diff and
+    /// merge.
+    ///
+    /// Requires the `unicode-normalization` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let mut tree = Tree::Node("cafe\u{301}".to_string(), vec![]);
+    /// tree.normalize_unicode();
+    /// assert_eq!(tree.label(), Some("caf\u{e9}"));
+    /// ```
+    #[cfg(feature = "unicode-normalization")]
+    pub fn normalize_unicode(&mut self) {
+        use unicode_normalization::UnicodeNormalization;
+        match self {
+            Tree::Node(label, children) => {
+                *label = label.nfc().collect();
+                for child in children {
+                    child.normalize_unicode();
+                }
+            }
+            Tree::Leaf(lines) => {
+                for line in lines {
+                    *line = line.nfc().collect();
+                }
+            }
+        }
+    }
+
+    /// Returns a copy of the tree that fits in `max_lines` rendered lines, keeping the
+    /// highest-weight subtrees.
+    ///
+    /// Starting from the root, the shown elements grow greedily: among the children
+    /// of shown nodes, the one with the highest `weight` (the earliest on ties) is
+    /// added next if its lines still fit in the budget, otherwise it stays hidden
+    /// and lighter candidates are tried. The hidden children of each shown node are
+    /// replaced by a single `… (N more)` summary leaf, which counts toward the
+    /// budget. Leaves are kept or hidden as a whole.
+    ///
+    /// The root line and its summary line are always kept, so budgets below two
+    /// lines may be exceeded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["minor".to_string()]),
+    ///     Tree::Leaf(vec!["major".to_string()]),
+    ///     Tree::Leaf(vec!["minor".to_string()]),
+    /// ]);
+    /// let fitted = tree.fit_to_lines(3, |t| u32::from(t.lines() == Some(&["major".to_string()][..])));
+    /// assert_eq!(fitted, Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["major".to_string()]),
+    ///     Tree::Leaf(vec!["… (2 more)".to_string()])
+    /// ]));
+    /// ```
+    pub fn fit_to_lines<W>(&self, max_lines: usize, weight: W) -> Tree
+    where
+        W: Fn(&Tree) -> u32,
+    {
+        let Tree::Node(_, root_children) = self else {
+            return self.clone();
+        };
+
+        // Lines taken by an element itself, plus one reserved for its summary leaf
+        let cost = |tree: &Tree| match tree {
+            Tree::Node(_, children) => 1 + usize::from(!children.is_empty()),
+            Tree::Leaf(lines) => lines.len(),
+        };
+
+        let mut shown = HashSet::from([Vec::new()]);
+        let mut hidden = HashMap::from([(Vec::new(), root_children.len())]);
+        let mut candidates = BinaryHeap::new();
+        let mut order = 0;
+        for (index, child) in root_children.iter().enumerate() {
+            candidates.push((weight(child), Reverse(order), vec![index]));
+            order += 1;
+        }
+
+        let mut used = cost(self);
+        while let Some((_, _, path)) = candidates.pop() {
+            let Some(tree) = path
+                .iter()
+                .try_fold(self, |tree, &index| tree.children()?.get(index))
+            else {
+                continue;
+            };
+            let parent = &path[..path.len() - 1];
+            // Showing the last hidden child frees the parent's summary line
+            let freed = usize::from(hidden[parent] == 1);
+            let needed = used + cost(tree) - freed;
+            if needed > max_lines {
+                continue;
+            }
+            used = needed;
+            if let Some(count) = hidden.get_mut(parent) {
+                *count -= 1;
+            }
+            if let Tree::Node(_, children) = tree {
+                hidden.insert(path.clone(), children.len());
+                for (index, child) in children.iter().enumerate() {
+                    let mut child_path = path.clone();
+                    child_path.push(index);
+                    candidates.push((weight(child), Reverse(order), child_path));
+                    order += 1;
+                }
+            }
+            shown.insert(path);
+        }
+
+        self.fitted(&mut Vec::new(), &shown)
+    }
+
+    fn fitted(&self, path: &mut Vec<usize>, shown: &HashSet<Vec<usize>>) -> Tree {
+        let Tree::Node(label, children) = self else {
+            return self.clone();
+        };
+        let mut kept = Vec::new();
+        let mut hidden = 0;
+        for (index, child) in children.iter().enumerate() {
+            path.push(index);
+            if shown.contains(path.as_slice()) {
+                kept.push(child.fitted(path, shown));
+            } else {
+                hidden += 1;
+            }
+            path.pop();
+        }
+        if hidden > 0 {
+            kept.push(Tree::Leaf(vec![format!("… ({hidden} more)")]));
+        }
+        Tree::Node(label.clone(), kept)
+    }
+}
+
+/// Trims each line and drops lines that are empty afterwards.
+fn canonical_lines(lines: &[String]) -> Vec<String> {
+    lines
+        .iter()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Orders canonical trees: leaves before nodes, leaves by lines, nodes by label then children.
+fn canonical_cmp(a: &Tree, b: &Tree) -> std::cmp::Ordering {
+    match (a, b) {
+        (Tree::Leaf(a), Tree::Leaf(b)) => a.cmp(b),
+        (Tree::Leaf(_), Tree::Node(..)) => std::cmp::Ordering::Less,
+        (Tree::Node(..), Tree::Leaf(_)) => std::cmp::Ordering::Greater,
+        (Tree::Node(a_label, a_children), Tree::Node(b_label, b_children)) => {
+            a_label.cmp(b_label).then_with(|| {
+                a_children
+                    .iter()
+                    .zip(b_children)
+                    .map(|(a, b)| canonical_cmp(a, b))
+                    .find(|ordering| ordering.is_ne())
+                    .unwrap_or_else(|| a_children.len().cmp(&b_children.len()))
+            })
+        }
+    }
 }
 
 #[cfg(test)]
@@ -199,6 +545,56 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_map_leaves_indexed() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Leaf(vec![
+                    "first".to_string(),
+                    "second".to_string(),
+                    "third".to_string(),
+                ]),
+                Tree::Leaf(vec!["other".to_string()]),
+            ],
+        );
+        let numbered = tree.map_leaves_indexed(|i, line| format!("{}. {line}", i + 1));
+        let children = numbered.children().unwrap();
+        assert_eq!(
+            children[0].lines(),
+            Some(
+                &[
+                    "1. first".to_string(),
+                    "2. second".to_string(),
+                    "3. third".to_string()
+                ][..]
+            )
+        );
+        assert_eq!(children[1].lines(), Some(&["1. other".to_string()][..]));
+    }
+
+    #[test]
+    fn test_rename_first() {
+        let mut tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node(
+                    "a".to_string(),
+                    vec![Tree::Node("target".to_string(), vec![])],
+                ),
+                Tree::Node("target".to_string(), vec![]),
+                Tree::Leaf(vec!["target".to_string()]),
+            ],
+        );
+        assert!(tree.rename_first("target", "renamed"));
+        let children = tree.children().unwrap();
+        assert_eq!(children[0].children().unwrap()[0].label(), Some("renamed"));
+        assert_eq!(children[1].label(), Some("target"));
+        assert_eq!(children[2].lines(), Some(&["target".to_string()][..]));
+
+        assert!(!tree.rename_first("missing", "renamed"));
+    }
+
     #[test]
     fn test_filter() {
         let tree = Tree::Node(
@@ -236,4 +632,190 @@ mod tests {
             assert_eq!(children.len(), 1);
         }
     }
+
+    #[test]
+    fn test_filter_to_matches() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node(
+                    "a".to_string(),
+                    vec![
+                        Tree::Node(
+                            "b".to_string(),
+                            vec![
+                                Tree::Leaf(vec!["target".to_string()]),
+                                Tree::Leaf(vec!["sibling".to_string()]),
+                            ],
+                        ),
+                        Tree::Leaf(vec!["other".to_string()]),
+                    ],
+                ),
+                Tree::Node("c".to_string(), vec![Tree::Leaf(vec!["x".to_string()])]),
+            ],
+        );
+        let is_target = |t: &Tree| t.lines().is_some_and(|lines| lines[0] == "target");
+        assert_eq!(
+            tree.filter_to_matches(is_target),
+            Some(Tree::Node(
+                "root".to_string(),
+                vec![Tree::Node(
+                    "a".to_string(),
+                    vec![Tree::Node(
+                        "b".to_string(),
+                        vec![Tree::Leaf(vec!["target".to_string()])],
+                    )],
+                )],
+            ))
+        );
+
+        let matched_node = tree.filter_to_matches(|t| t.label() == Some("b")).unwrap();
+        assert_eq!(
+            matched_node.children().unwrap()[0].children().unwrap()[0],
+            tree.children().unwrap()[0].children().unwrap()[0]
+        );
+
+        assert_eq!(
+            tree.filter_to_matches(|t| t.label() == Some("missing")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_canonicalize() {
+        let first = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node("b".to_string(), vec![Tree::Leaf(vec!["x".to_string()])]),
+                Tree::Leaf(vec!["  two".to_string()]),
+                Tree::Node(
+                    " a".to_string(),
+                    vec![
+                        Tree::Leaf(vec!["2".to_string()]),
+                        Tree::Leaf(vec!["1".to_string()]),
+                    ],
+                ),
+                Tree::Node("empty".to_string(), vec![]),
+                Tree::Leaf(vec!["one".to_string(), "   ".to_string()]),
+            ],
+        );
+        let second = Tree::Node(
+            "root ".to_string(),
+            vec![
+                Tree::Leaf(vec!["one".to_string(), "two".to_string()]),
+                Tree::Node(
+                    "a".to_string(),
+                    vec![Tree::Leaf(vec!["1".to_string(), "2".to_string()])],
+                ),
+                Tree::Node(
+                    "b".to_string(),
+                    vec![
+                        Tree::Leaf(vec![]),
+                        Tree::Leaf(vec!["x ".to_string()]),
+                        Tree::Node(
+                            "gone".to_string(),
+                            vec![Tree::Node("empty".to_string(), vec![])],
+                        ),
+                    ],
+                ),
+            ],
+        );
+
+        let canonical = first.canonicalize();
+        assert_eq!(canonical, second.canonicalize());
+        assert_eq!(canonical.canonicalize(), canonical);
+        assert_eq!(
+            canonical,
+            Tree::Node(
+                "root".to_string(),
+                vec![
+                    Tree::Leaf(vec!["one".to_string(), "two".to_string()]),
+                    Tree::Node(
+                        "a".to_string(),
+                        vec![Tree::Leaf(vec!["1".to_string(), "2".to_string()])],
+                    ),
+                    Tree::Node("b".to_string(), vec![Tree::Leaf(vec!["x".to_string()])]),
+                ],
+            )
+        );
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    #[test]
+    fn test_normalize_unicode() {
+        let mut tree = Tree::Node(
+            "re\u{301}sume\u{301}".to_string(),
+            vec![Tree::Leaf(vec![
+                "n\u{303}".to_string(),
+                "plain".to_string(),
+            ])],
+        );
+        tree.normalize_unicode();
+        assert_eq!(
+            tree,
+            Tree::Node(
+                "r\u{e9}sum\u{e9}".to_string(),
+                vec![Tree::Leaf(vec!["\u{f1}".to_string(), "plain".to_string()])],
+            )
+        );
+    }
+
+    #[test]
+    fn test_fit_to_lines() {
+        let branch = |label: &str, count: usize| {
+            Tree::Node(
+                label.to_string(),
+                (0..count)
+                    .map(|i| Tree::Leaf(vec![format!("{label}{i}")]))
+                    .collect(),
+            )
+        };
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![branch("light", 2), branch("heavy", 4)],
+        );
+        let weight = |tree: &Tree| tree.children().map_or(0, |children| children.len() as u32);
+
+        let fitted = tree.fit_to_lines(8, weight);
+        assert_eq!(
+            fitted,
+            Tree::Node(
+                "root".to_string(),
+                vec![
+                    Tree::Node(
+                        "light".to_string(),
+                        vec![Tree::Leaf(vec!["… (2 more)".to_string()])],
+                    ),
+                    branch("heavy", 4),
+                ],
+            )
+        );
+        assert_eq!(fitted.render_to_string().lines().count(), 8);
+
+        let fitted = tree.fit_to_lines(4, weight);
+        assert_eq!(
+            fitted,
+            Tree::Node(
+                "root".to_string(),
+                vec![
+                    Tree::Node(
+                        "heavy".to_string(),
+                        vec![Tree::Leaf(vec!["… (4 more)".to_string()])],
+                    ),
+                    Tree::Leaf(vec!["… (1 more)".to_string()]),
+                ],
+            )
+        );
+        assert_eq!(fitted.render_to_string().lines().count(), 4);
+
+        assert_eq!(
+            tree.fit_to_lines(3, weight),
+            Tree::Node(
+                "root".to_string(),
+                vec![Tree::Leaf(vec!["… (2 more)".to_string()])],
+            )
+        );
+
+        assert_eq!(tree.fit_to_lines(100, weight), tree);
+    }
 }
@@ -23,6 +23,69 @@ impl Tree {
         PreOrder::new(self)
     }
 
+    /// Returns an iterator over mutable labels and lines in pre-order.
+    ///
+    /// Requires the `traversal` feature.
+    ///
+    /// Each node yields its label and each leaf its lines, in the same order as
+    /// [`pre_order`](Self::pre_order). The tree structure itself can't change
+    /// during iteration; use [`for_each_mut`](Self::for_each_mut) to modify whole
+    /// elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::{Tree, traversal::ElementMut};
+    ///
+    /// let mut tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["item".to_string()])
+    /// ]);
+    /// for element in tree.iter_mut() {
+    ///     if let ElementMut::Label(label) = element {
+    ///         label.push('/');
+    ///     }
+    /// }
+    /// assert_eq!(tree.label(), Some("root/"));
+    /// ```
+    pub fn iter_mut(&mut self) -> IterMut<'_> {
+        IterMut::new(self)
+    }
+
+    /// Calls `f` on every element in pre-order, allowing each to be modified.
+    ///
+    /// Requires the `traversal` feature.
+    ///
+    /// A node is passed to `f` before its children, so changes `f` makes to a
+    /// node's children (such as adding or removing them) decide which children
+    /// are visited next.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let mut tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["item".to_string()])
+    /// ]);
+    /// tree.for_each_mut(&mut |element| {
+    ///     if element.is_leaf() {
+    ///         *element = Tree::Leaf(vec!["replaced".to_string()]);
+    ///     }
+    /// });
+    /// assert_eq!(tree.children().unwrap()[0].lines(), Some(&["replaced".to_string()][..]));
+    /// ```
+    pub fn for_each_mut<F>(&mut self, f: &mut F)
+    where
+        F: FnMut(&mut Tree),
+    {
+        f(self);
+        if let Tree::Node(_, children) = self {
+            for child in children.iter_mut() {
+                child.for_each_mut(f);
+            }
+        }
+    }
+
     /// Returns an iterator that traverses the tree in post-order (children, then root).
     ///
     /// Requires the `traversal` feature.
@@ -102,10 +165,56 @@ impl Tree {
     pub fn leaves(&self) -> Leaves<'_> {
         Leaves::new(self)
     }
+
+    /// Returns the first leaf in pre-order, or `None` if the tree has no leaves.
+    ///
+    /// Requires the `traversal` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["first".to_string()]),
+    ///     Tree::Leaf(vec!["last".to_string()]),
+    /// ]);
+    /// assert_eq!(tree.first_leaf(), Some(&Tree::Leaf(vec!["first".to_string()])));
+    /// ```
+    pub fn first_leaf(&self) -> Option<&Tree> {
+        self.leaves().next()
+    }
+
+    /// Returns the last leaf in pre-order, or `None` if the tree has no leaves.
+    ///
+    /// Walks the tree from the right instead of exhausting the [`Leaves`] iterator.
+    ///
+    /// Requires the `traversal` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["first".to_string()]),
+    ///     Tree::Leaf(vec!["last".to_string()]),
+    /// ]);
+    /// assert_eq!(tree.last_leaf(), Some(&Tree::Leaf(vec!["last".to_string()])));
+    /// ```
+    pub fn last_leaf(&self) -> Option<&Tree> {
+        match self {
+            Tree::Leaf(_) => Some(self),
+            Tree::Node(_, children) => children.iter().rev().find_map(|child| child.last_leaf()),
+        }
+    }
 }
 
 /// An iterator that traverses a tree in pre-order (root, then children).
 ///
+/// Children are visited left to right, matching the order in which elements
+/// appear in rendered output and in the line iterator of the `iterator` feature.
+///
 /// # Examples
 ///
 /// ```
@@ -142,6 +251,61 @@ impl<'a> Iterator for PreOrder<'a> {
     }
 }
 
+/// Mutable access to the content of one element, yielded by [`IterMut`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum ElementMut<'a> {
+    /// The label of a node
+    Label(&'a mut String),
+    /// The lines of a leaf
+    Lines(&'a mut Vec<String>),
+}
+
+/// An iterator over mutable labels and lines in pre-order.
+///
+/// Yielding the content rather than the elements themselves lets labels and
+/// lines change while keeping the children of yielded nodes borrowed by the
+/// iterator.
+///
+/// # Examples
+///
+/// ```
+/// use treelog::{Tree, traversal::{ElementMut, IterMut}};
+///
+/// let mut tree = Tree::Node("root".to_string(), vec![
+///     Tree::Leaf(vec!["item".to_string()])
+/// ]);
+/// for element in IterMut::new(&mut tree) {
+///     if let ElementMut::Lines(lines) = element {
+///         lines.push("more".to_string());
+///     }
+/// }
+/// ```
+pub struct IterMut<'a> {
+    stack: Vec<&'a mut Tree>,
+}
+
+impl<'a> IterMut<'a> {
+    /// Creates a new mutable pre-order iterator.
+    pub fn new(tree: &'a mut Tree) -> Self {
+        IterMut { stack: vec![tree] }
+    }
+}
+
+impl<'a> Iterator for IterMut<'a> {
+    type Item = ElementMut<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.stack.pop()? {
+            Tree::Node(label, children) => {
+                // Push children in reverse order so we process them left-to-right
+                self.stack.extend(children.iter_mut().rev());
+                Some(ElementMut::Label(label))
+            }
+            Tree::Leaf(lines) => Some(ElementMut::Lines(lines)),
+        }
+    }
+}
+
 /// An iterator that traverses a tree in post-order (children, then root).
 ///
 /// # Examples
@@ -369,4 +533,95 @@ mod tests {
         assert_eq!(leaves.len(), 2);
         assert!(leaves.iter().all(|l| l.is_leaf()));
     }
+
+    #[test]
+    fn test_first_and_last_leaf() {
+        let tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node(
+                    "a".to_string(),
+                    vec![
+                        Tree::Leaf(vec!["a1".to_string()]),
+                        Tree::Leaf(vec!["a2".to_string()]),
+                    ],
+                ),
+                Tree::Leaf(vec!["b".to_string()]),
+                Tree::Node("c".to_string(), vec![]),
+            ],
+        );
+        assert_eq!(tree.first_leaf(), Some(&Tree::Leaf(vec!["a1".to_string()])));
+        assert_eq!(tree.last_leaf(), Some(&Tree::Leaf(vec!["b".to_string()])));
+
+        let empty = Tree::Node(
+            "root".to_string(),
+            vec![Tree::Node("child".to_string(), vec![])],
+        );
+        assert_eq!(empty.first_leaf(), None);
+        assert_eq!(empty.last_leaf(), None);
+    }
+
+    #[test]
+    fn test_iter_mut() {
+        let mut tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node("a".to_string(), vec![Tree::Leaf(vec!["a1".to_string()])]),
+                Tree::Node("b".to_string(), vec![]),
+            ],
+        );
+        let mut visited = Vec::new();
+        for element in tree.iter_mut() {
+            match element {
+                ElementMut::Label(label) => {
+                    visited.push(label.clone());
+                    *label = label.to_uppercase();
+                }
+                ElementMut::Lines(lines) => visited.push(lines.join("\n")),
+            }
+        }
+        assert_eq!(visited, vec!["root", "a", "a1", "b"]);
+        assert_eq!(
+            tree,
+            Tree::Node(
+                "ROOT".to_string(),
+                vec![
+                    Tree::Node("A".to_string(), vec![Tree::Leaf(vec!["a1".to_string()])]),
+                    Tree::Node("B".to_string(), vec![]),
+                ],
+            )
+        );
+    }
+
+    #[test]
+    fn test_for_each_mut() {
+        let mut tree = Tree::Node(
+            "root".to_string(),
+            vec![
+                Tree::Node("a".to_string(), vec![Tree::Leaf(vec!["a1".to_string()])]),
+                Tree::Leaf(vec!["b".to_string()]),
+            ],
+        );
+        let mut count = 0;
+        tree.for_each_mut(&mut |element| {
+            count += 1;
+            if let Tree::Node(label, children) = element {
+                *label = label.to_uppercase();
+                if label == "A" {
+                    children.push(Tree::Leaf(vec!["added".to_string()]));
+                }
+            }
+        });
+        assert_eq!(count, 5);
+        assert_eq!(
+            tree.children().unwrap()[0],
+            Tree::Node(
+                "A".to_string(),
+                vec![
+                    Tree::Leaf(vec!["a1".to_string()]),
+                    Tree::Leaf(vec!["added".to_string()]),
+                ],
+            )
+        );
+    }
 }
@@ -1,5 +1,6 @@
 //! Core tree data structure for representing hierarchical data.
 
+use std::collections::HashMap;
 use std::fmt;
 
 /// A tree structure that can represent hierarchical data with nodes and leaves.
@@ -37,6 +38,86 @@ pub enum Tree {
     Leaf(Vec<String>),
 }
 
+/// Content in a label or leaf line that breaks the alignment of rendered trees.
+///
+/// Returned by [`Tree::render_issues`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RenderIssue {
+    /// A line feed, which starts a new line without the tree prefix
+    Newline,
+    /// A carriage return, which moves the cursor back over the tree prefix
+    CarriageReturn,
+    /// A zero-width or invisible formatting character, such as a soft hyphen,
+    /// counted as a column while occupying none
+    ZeroWidth(char),
+    /// A bidirectional control character, which can reorder the rest of the line
+    Bidi(char),
+    /// A combining character, drawn in the same column as the character before it
+    Combining(char),
+    /// An escape character, such as one starting an ANSI color sequence
+    AnsiEscape,
+    /// Any other C0 or C1 control character, including tab and delete, whose
+    /// width depends on the terminal
+    Control(char),
+}
+
+impl RenderIssue {
+    /// Returns the issue caused by `c`, if any.
+    ///
+    /// Combining marks are recognized in the common combining blocks and in the
+    /// nonspacing marks of the Cyrillic, Hebrew, Arabic, Devanagari and Thai
+    /// scripts, plus variation selectors; this is a table, not a full lookup of
+    /// the Unicode general category.
+    fn of(c: char) -> Option<Self> {
+        match c {
+            '\n' => Some(RenderIssue::Newline),
+            '\r' => Some(RenderIssue::CarriageReturn),
+            '\x1b' => Some(RenderIssue::AnsiEscape),
+            '\0'..='\x1f' | '\x7f'..='\u{9f}' => Some(RenderIssue::Control(c)),
+            '\u{00AD}' | '\u{200B}'..='\u{200D}' | '\u{2060}'..='\u{2064}' | '\u{FEFF}' => {
+                Some(RenderIssue::ZeroWidth(c))
+            }
+            '\u{061C}'
+            | '\u{200E}'
+            | '\u{200F}'
+            | '\u{202A}'..='\u{202E}'
+            | '\u{2066}'..='\u{2069}' => Some(RenderIssue::Bidi(c)),
+            '\u{0300}'..='\u{036F}'
+            | '\u{0483}'..='\u{0489}'
+            | '\u{0591}'..='\u{05BD}'
+            | '\u{05BF}'
+            | '\u{05C1}'..='\u{05C2}'
+            | '\u{05C4}'..='\u{05C5}'
+            | '\u{05C7}'
+            | '\u{0610}'..='\u{061A}'
+            | '\u{064B}'..='\u{065F}'
+            | '\u{0670}'
+            | '\u{06D6}'..='\u{06DC}'
+            | '\u{06DF}'..='\u{06E4}'
+            | '\u{06E7}'..='\u{06E8}'
+            | '\u{06EA}'..='\u{06ED}'
+            | '\u{0900}'..='\u{0902}'
+            | '\u{093A}'
+            | '\u{093C}'
+            | '\u{0941}'..='\u{0948}'
+            | '\u{094D}'
+            | '\u{0951}'..='\u{0957}'
+            | '\u{0962}'..='\u{0963}'
+            | '\u{0E31}'
+            | '\u{0E34}'..='\u{0E3A}'
+            | '\u{0E47}'..='\u{0E4E}'
+            | '\u{1AB0}'..='\u{1AFF}'
+            | '\u{1DC0}'..='\u{1DFF}'
+            | '\u{20D0}'..='\u{20FF}'
+            | '\u{3099}'..='\u{309A}'
+            | '\u{FE00}'..='\u{FE0F}'
+            | '\u{FE20}'..='\u{FE2F}'
+            | '\u{E0100}'..='\u{E01EF}' => Some(RenderIssue::Combining(c)),
+            _ => None,
+        }
+    }
+}
+
 impl Tree {
     /// Creates a new node with the given label and no children.
     ///
@@ -52,6 +133,24 @@ impl Tree {
         Tree::Node(label.into(), Vec::new())
     }
 
+    /// Creates a new node with the given label and space reserved for `capacity` children.
+    ///
+    /// Useful when building wide nodes from pre-sized data, as it avoids
+    /// reallocating the children vector while they are added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let mut node = Tree::node_with_capacity("root", 3);
+    /// node.add_child(Tree::new_leaf("child"));
+    /// ```
+    #[inline]
+    pub fn node_with_capacity(label: impl Into<String>, capacity: usize) -> Self {
+        Tree::Node(label.into(), Vec::with_capacity(capacity))
+    }
+
     /// Creates a new leaf with a single line of text.
     ///
     /// # Examples
@@ -80,6 +179,21 @@ impl Tree {
         Tree::Leaf(lines.into_iter().map(Into::into).collect())
     }
 
+    /// Wraps this tree as the sole child of a new node with the given label.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let tree = Tree::new_leaf("item").under("root");
+    /// assert_eq!(tree, Tree::Node("root".to_string(), vec![Tree::new_leaf("item")]));
+    /// ```
+    #[inline]
+    pub fn under(self, label: impl Into<String>) -> Self {
+        Tree::Node(label.into(), vec![self])
+    }
+
     /// Adds a child to this tree. Returns `Some(self)` if this is a node, `None` otherwise.
     ///
     /// # Examples
@@ -168,6 +282,54 @@ impl Tree {
         }
     }
 
+    /// Returns the height in lines of every subtree, keyed by its path (child indices from the root).
+    ///
+    /// A node is one line plus the heights of its children, and a leaf is one line
+    /// per text line, matching the default text rendering and the SVG layout.
+    /// All heights are computed in a single post-order pass, so callers can look
+    /// them up instead of re-measuring each subtree at every level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::Tree;
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["line1".to_string(), "line2".to_string()]),
+    ///     Tree::Node("child".to_string(), vec![]),
+    /// ]);
+    /// let heights = tree.subtree_heights();
+    /// assert_eq!(heights[&vec![]], 4);
+    /// assert_eq!(heights[&vec![0]], 2);
+    /// assert_eq!(heights[&vec![1]], 1);
+    /// ```
+    pub fn subtree_heights(&self) -> HashMap<Vec<usize>, usize> {
+        let mut heights = HashMap::new();
+        self.collect_heights(&mut Vec::new(), &mut heights);
+        heights
+    }
+
+    fn collect_heights(
+        &self,
+        path: &mut Vec<usize>,
+        heights: &mut HashMap<Vec<usize>, usize>,
+    ) -> usize {
+        let height = match self {
+            Tree::Node(_, children) => {
+                let mut height = 1;
+                for (index, child) in children.iter().enumerate() {
+                    path.push(index);
+                    height += child.collect_heights(path, heights);
+                    path.pop();
+                }
+                height
+            }
+            Tree::Leaf(lines) => lines.len(),
+        };
+        heights.insert(path.clone(), height);
+        height
+    }
+
     /// Validates the tree structure.
     ///
     /// Returns `Ok(())` if the tree is valid, or an error message if invalid.
@@ -191,6 +353,62 @@ impl Tree {
         Ok(())
     }
 
+    /// Lists content that would break the alignment of the rendered tree.
+    ///
+    /// Every label and leaf line is checked for line feeds, carriage returns,
+    /// zero-width, bidirectional control and combining characters, escape
+    /// characters such as those starting ANSI sequences, and other control
+    /// characters such as tabs. Each issue is reported once per element, with the
+    /// element's path (child indices from the root), in pre-order. The tree is not
+    /// modified, so callers can decide whether to sanitize or reject it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use treelog::{RenderIssue, Tree};
+    ///
+    /// let tree = Tree::Node("root".to_string(), vec![
+    ///     Tree::Leaf(vec!["\x1b[31mred\x1b[0m".to_string()]),
+    ///     Tree::Leaf(vec!["fine".to_string()])
+    /// ]);
+    /// assert_eq!(tree.render_issues(), vec![(vec![0], RenderIssue::AnsiEscape)]);
+    /// ```
+    pub fn render_issues(&self) -> Vec<(Vec<usize>, RenderIssue)> {
+        let mut issues = Vec::new();
+        self.collect_render_issues(&mut Vec::new(), &mut issues);
+        issues
+    }
+
+    fn collect_render_issues(
+        &self,
+        path: &mut Vec<usize>,
+        issues: &mut Vec<(Vec<usize>, RenderIssue)>,
+    ) {
+        let mut found: Vec<RenderIssue> = Vec::new();
+        let texts = match self {
+            Tree::Node(label, _) => std::slice::from_ref(label),
+            Tree::Leaf(lines) => lines.as_slice(),
+        };
+        for issue in texts
+            .iter()
+            .flat_map(|text| text.chars())
+            .filter_map(RenderIssue::of)
+        {
+            if !found.contains(&issue) {
+                found.push(issue);
+            }
+        }
+        issues.extend(found.into_iter().map(|issue| (path.clone(), issue)));
+
+        if let Tree::Node(_, children) = self {
+            for (index, child) in children.iter().enumerate() {
+                path.push(index);
+                child.collect_render_issues(path, issues);
+                path.pop();
+            }
+        }
+    }
+
     /// Checks if the tree is valid.
     ///
     /// Returns `true` if valid, `false` otherwise.
@@ -251,9 +469,167 @@ mod tests {
         assert_eq!(node.child_count(), Some(1));
     }
 
+    #[test]
+    fn test_node_with_capacity() {
+        let mut node = Tree::node_with_capacity("root", 10_000);
+        if let Tree::Node(_, children) = &node {
+            assert!(children.capacity() >= 10_000);
+        }
+        for i in 0..10_000 {
+            node.add_child(Tree::new_leaf(i.to_string()));
+        }
+        assert_eq!(node.child_count(), Some(10_000));
+        assert_eq!(node.children().unwrap()[9_999], Tree::new_leaf("9999"));
+    }
+
     #[test]
     fn test_add_child_to_leaf() {
         let mut leaf = Tree::new_leaf("leaf");
         assert!(leaf.add_child(Tree::new_leaf("child")).is_none());
     }
+
+    #[test]
+    fn test_under() {
+        let tree = Tree::Node("src".to_string(), vec![Tree::new_leaf("main.rs")]);
+        let wrapped = tree.clone().under("project");
+        assert_eq!(wrapped.label(), Some("project"));
+        assert_eq!(wrapped.children(), Some(&[tree][..]));
+    }
+
+    #[test]
+    fn test_subtree_heights() {
+        fn height(tree: &Tree) -> usize {
+            match tree {
+                Tree::Node(_, children) => 1 + children.iter().map(height).sum::<usize>(),
+                Tree::Leaf(lines) => lines.len(),
+            }
+        }
+        fn check(tree: &Tree, path: &mut Vec<usize>, heights: &HashMap<Vec<usize>, usize>) {
+            assert_eq!(heights[path.as_slice()], height(tree));
+            for (index, child) in tree.children().unwrap_or_default().iter().enumerate() {
+                path.push(index);
+                check(child, path, heights);
+                path.pop();
+            }
+        }
+
+        let mut tree = Tree::Leaf(vec!["bottom".to_string(), "more".to_string()]);
+        for depth in 0..200 {
+            tree = Tree::Node(
+                format!("level{depth}"),
+                vec![
+                    Tree::Leaf(vec!["a".to_string()]),
+                    tree,
+                    Tree::Node("empty".to_string(), vec![]),
+                ],
+            );
+        }
+        let heights = tree.subtree_heights();
+        assert_eq!(heights.len(), 1 + 200 * 3);
+        assert_eq!(heights[&vec![]], 2 + 200 * 3);
+        check(&tree, &mut Vec::new(), &heights);
+    }
+
+    #[test]
+    fn test_render_issues() {
+        let issues = |text: &str| Tree::new_leaf(text).render_issues();
+        assert!(issues("plain text and ünïcode, 中文, עברית").is_empty());
+        assert_eq!(issues("a\nb"), vec![(vec![], RenderIssue::Newline)]);
+        assert_eq!(
+            issues("progress\r100%"),
+            vec![(vec![], RenderIssue::CarriageReturn)]
+        );
+        assert_eq!(
+            issues("zero\u{200B}width\u{FEFF}"),
+            vec![
+                (vec![], RenderIssue::ZeroWidth('\u{200B}')),
+                (vec![], RenderIssue::ZeroWidth('\u{FEFF}')),
+            ]
+        );
+        assert_eq!(
+            issues("cafe\u{0301}"),
+            vec![(vec![], RenderIssue::Combining('\u{0301}'))]
+        );
+        assert_eq!(
+            issues("\x1b[1mbold\x1b[0m"),
+            vec![(vec![], RenderIssue::AnsiEscape)]
+        );
+    }
+
+    #[test]
+    fn test_render_issues_controls() {
+        let issues = |text: &str| Tree::new_leaf(text).render_issues();
+        assert_eq!(
+            issues("tab\there, back\x08space, del\x7f, next\u{85}line"),
+            vec![
+                (vec![], RenderIssue::Control('\t')),
+                (vec![], RenderIssue::Control('\x08')),
+                (vec![], RenderIssue::Control('\x7f')),
+                (vec![], RenderIssue::Control('\u{85}')),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_issues_bidi() {
+        let issues = |text: &str| Tree::new_leaf(text).render_issues();
+        for c in [
+            '\u{200E}', '\u{200F}', '\u{202A}', '\u{202E}', '\u{2066}', '\u{2069}',
+        ] {
+            assert_eq!(
+                issues(&format!("abc{c}def")),
+                vec![(vec![], RenderIssue::Bidi(c))]
+            );
+        }
+    }
+
+    #[test]
+    fn test_render_issues_soft_hyphen() {
+        assert_eq!(
+            Tree::new_leaf("hyphen\u{AD}ation").render_issues(),
+            vec![(vec![], RenderIssue::ZeroWidth('\u{AD}'))]
+        );
+    }
+
+    #[test]
+    fn test_render_issues_combining_scripts() {
+        let issues = |text: &str| Tree::new_leaf(text).render_issues();
+        for (text, mark) in [
+            ("\u{0418}\u{0486}", '\u{0486}'),
+            ("\u{05E9}\u{05B8}", '\u{05B8}'),
+            ("\u{0628}\u{064E}", '\u{064E}'),
+            ("\u{0915}\u{093F}\u{094D}", '\u{094D}'),
+            ("\u{0E01}\u{0E34}", '\u{0E34}'),
+            ("\u{304B}\u{3099}", '\u{3099}'),
+            ("\u{2764}\u{FE0F}", '\u{FE0F}'),
+        ] {
+            assert_eq!(issues(text), vec![(vec![], RenderIssue::Combining(mark))]);
+        }
+    }
+
+    #[test]
+    fn test_render_issues_paths() {
+        let tree = Tree::Node(
+            "root\r".to_string(),
+            vec![
+                Tree::Leaf(vec!["ok".to_string(), "line\nbreak".to_string()]),
+                Tree::Node(
+                    "child".to_string(),
+                    vec![Tree::Leaf(vec![
+                        "\x1b[31ma\x1b[0m".to_string(),
+                        "b\nc".to_string(),
+                    ])],
+                ),
+            ],
+        );
+        assert_eq!(
+            tree.render_issues(),
+            vec![
+                (vec![], RenderIssue::CarriageReturn),
+                (vec![0], RenderIssue::Newline),
+                (vec![1, 0], RenderIssue::AnsiEscape),
+                (vec![1, 0], RenderIssue::Newline),
+            ]
+        );
+    }
 }
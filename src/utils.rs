@@ -26,6 +26,45 @@ pub(crate) fn estimate_capacity(tree: &crate::tree::Tree, avg_line_len: usize) -
     (nodes + lines) * (10 + avg_line_len + 1)
 }
 
+/// Replaces tabs with spaces up to the next multiple of `tab_width` columns.
+///
+/// `s` is assumed to start at column `start`, so tab stops line up with the
+/// rest of the rendered line. A `tab_width` of 0 removes tabs.
+pub(crate) fn expand_tabs(s: &str, tab_width: usize, start: usize) -> String {
+    let mut expanded = String::with_capacity(s.len());
+    let mut column = start;
+    for c in s.chars() {
+        if c == '\t' {
+            let spaces = tab_stop(column, tab_width) - column;
+            expanded.extend(std::iter::repeat_n(' ', spaces));
+            column += spaces;
+        } else {
+            expanded.push(c);
+            column += 1;
+        }
+    }
+    expanded
+}
+
+/// Returns the number of columns `s` occupies when it starts at column `start`,
+/// with tabs expanded to `tab_width`.
+pub(crate) fn display_width(s: &str, tab_width: usize, start: usize) -> usize {
+    let end = s.chars().fold(start, |column, c| {
+        if c == '\t' {
+            tab_stop(column, tab_width)
+        } else {
+            column + 1
+        }
+    });
+    end - start
+}
+
+fn tab_stop(column: usize, tab_width: usize) -> usize {
+    column
+        .checked_div(tab_width)
+        .map_or(column, |stops| (stops + 1) * tab_width)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -43,4 +82,21 @@ mod tests {
         let capacity = estimate_capacity(&tree, 10);
         assert!(capacity > 0);
     }
+
+    #[test]
+    fn test_expand_tabs() {
+        assert_eq!(expand_tabs("a\tb", 4, 0), "a   b");
+        assert_eq!(expand_tabs("abcd\tb", 4, 0), "abcd    b");
+        assert_eq!(expand_tabs("\t\tx", 2, 0), "    x");
+        assert_eq!(expand_tabs("a\tb", 4, 3), "a    b");
+        assert_eq!(expand_tabs("a\tb", 0, 0), "ab");
+        for s in ["a\tb", "abcd\tb", "\t\tx", "plain"] {
+            for start in 0..4 {
+                assert_eq!(
+                    display_width(s, 4, start),
+                    expand_tabs(s, 4, start).chars().count()
+                );
+            }
+        }
+    }
 }